@@ -0,0 +1,154 @@
+//! Generates the C header for `src/lib.rs`.
+//!
+//! This is a line-based scan rather than a real parser: it picks up doc comments,
+//! `pub const` integers, the opaque `pub struct` and every `extern "C"` function, and
+//! knows only the few types the interface uses.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+fn c_type(ty: &str) -> String {
+    let ty = ty.trim();
+    if let Some(inner) = ty.strip_prefix("*const ") {
+        return format!("const {} *", c_type(inner));
+    }
+    if let Some(inner) = ty.strip_prefix("*mut ") {
+        return format!("{} *", c_type(inner));
+    }
+    match ty {
+        "" => "void",
+        "i32" => "int32_t",
+        "i64" => "int64_t",
+        "usize" => "size_t",
+        "c_char" => "char",
+        other => other,
+    }
+    .into()
+}
+
+fn c_decl(ty: &str, name: &str) -> String {
+    let ty = c_type(ty);
+    if ty.ends_with('*') {
+        format!("{}{}", ty, name)
+    } else {
+        format!("{} {}", ty, name)
+    }
+}
+
+fn c_function(signature: &str) -> String {
+    let open = signature.find('(').unwrap();
+    let close = signature.rfind(')').unwrap();
+    let name = signature[..open].rsplit(' ').next().unwrap();
+    let ret = signature[close + 1..]
+        .trim_end_matches('{')
+        .trim()
+        .trim_start_matches("->");
+    let args: Vec<_> = signature[open + 1..close]
+        .split(',')
+        .map(str::trim)
+        .filter(|arg| !arg.is_empty())
+        .map(|arg| {
+            let colon = arg.find(':').unwrap();
+            c_decl(&arg[colon + 1..], arg[..colon].trim())
+        })
+        .collect();
+    let args = if args.is_empty() {
+        "void".into()
+    } else {
+        args.join(", ")
+    };
+    format!("{}({});", c_decl(ret, name), args)
+}
+
+pub fn generate(source: &str) -> String {
+    let mut out = vec![
+        "/* Generated from ffi/src/lib.rs by build.rs, do not edit. */".to_string(),
+        String::new(),
+        "#ifndef AOC19_INTCODE_H".into(),
+        "#define AOC19_INTCODE_H".into(),
+        String::new(),
+        "#include <stddef.h>".into(),
+        "#include <stdint.h>".into(),
+        String::new(),
+        "#ifdef __cplusplus".into(),
+        "extern \"C\" {".into(),
+        "#endif".into(),
+        String::new(),
+    ];
+    let mut docs = Vec::new();
+    let mut lines = source.lines().map(str::trim);
+    while let Some(line) = lines.next() {
+        if line.starts_with("//!") {
+            continue;
+        } else if let Some(doc) = line.strip_prefix("///") {
+            docs.push(doc.trim().to_string());
+            continue;
+        }
+        let item = if let Some(rest) = line.strip_prefix("pub const ") {
+            let (name, value) = (rest.split(':').next().unwrap(), rest.split('=').nth(1));
+            let value = value.unwrap().trim().trim_end_matches(';');
+            if value.starts_with('-') {
+                Some(format!("#define {} ({})", name, value))
+            } else {
+                Some(format!("#define {} {}", name, value))
+            }
+        } else if let Some(rest) = line.strip_prefix("pub struct ") {
+            let name = rest.trim_end_matches(['{', ' ']);
+            Some(format!("typedef struct {} {};", name, name))
+        } else if line.contains("extern \"C\" fn ") {
+            let mut signature = line.to_string();
+            while !signature.ends_with('{') {
+                signature.push_str(lines.next().unwrap());
+            }
+            Some(c_function(&signature.replace(",)", ")")))
+        } else {
+            None
+        };
+        if let Some(item) = item {
+            let mut docs: Vec<_> = docs
+                .iter()
+                .map(String::as_str)
+                .take_while(|&doc| doc != "# Safety")
+                .collect();
+            while docs.last() == Some(&"") {
+                docs.pop();
+            }
+            match docs.len() {
+                0 => (),
+                1 => out.push(format!("/* {} */", docs[0])),
+                _ => {
+                    out.push("/*".into());
+                    docs.iter()
+                        .for_each(|doc| out.push(format!(" * {}", doc).trim_end().into()));
+                    out.push(" */".into());
+                }
+            }
+            out.push(item);
+            out.push(String::new());
+        }
+        if !line.starts_with("#[") {
+            docs.clear();
+        }
+    }
+    out.extend(vec![
+        "#ifdef __cplusplus".into(),
+        "}".into(),
+        "#endif".into(),
+        String::new(),
+        "#endif /* AOC19_INTCODE_H */".into(),
+        String::new(),
+    ]);
+    out.join("\n")
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    let source = fs::read_to_string("src/lib.rs").unwrap();
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(
+        Path::new(&out_dir).join("aoc19_intcode.h"),
+        generate(&source),
+    )
+    .unwrap();
+}
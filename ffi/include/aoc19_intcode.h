@@ -0,0 +1,67 @@
+/* Generated from ffi/src/lib.rs by build.rs, do not edit. */
+
+#ifndef AOC19_INTCODE_H
+#define AOC19_INTCODE_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The machine executed an instruction and can keep going. */
+#define INTCODE_RUNNING 0
+
+/* The machine produced an output, available through `intcode_pop_output`. */
+#define INTCODE_OUTPUT 1
+
+/* The machine is waiting for `intcode_push_input`. */
+#define INTCODE_NEEDS_INPUT 2
+
+/* The program has exited. */
+#define INTCODE_HALTED 3
+
+/* No program is loaded, or it hit an invalid instruction or address. */
+#define INTCODE_ERROR (-1)
+
+/* Opaque handle to a machine along with its input and output queues. */
+typedef struct IntcodeMachine IntcodeMachine;
+
+/* Creates an empty machine; load a program before stepping it. */
+IntcodeMachine *intcode_create(void);
+
+/* Loads a copy of `len` cells at `data`, resetting the machine and its queues. */
+int32_t intcode_load(IntcodeMachine *machine, const int64_t *data, size_t len);
+
+/* Parses a comma-separated program and loads it like `intcode_load`. */
+int32_t intcode_load_text(IntcodeMachine *machine, const char *source);
+
+/* Executes a single instruction and returns one of the `INTCODE_*` statuses. */
+int32_t intcode_step(IntcodeMachine *machine);
+
+/*
+ * Runs until the program exits, needs an input, or fails.
+ *
+ * Returns `INTCODE_HALTED`, `INTCODE_NEEDS_INPUT` or `INTCODE_ERROR`; outputs produced
+ * along the way are queued.
+ */
+int32_t intcode_run(IntcodeMachine *machine);
+
+/* Queues a value to be consumed by the next input instruction. */
+void intcode_push_input(IntcodeMachine *machine, int64_t value);
+
+/* Takes the oldest queued output into `value`; returns 1 on success, 0 if none is left. */
+int32_t intcode_pop_output(IntcodeMachine *machine, int64_t *value);
+
+/* Reads memory cell `address` into `value`; returns 1 on success, 0 if out of bounds. */
+int32_t intcode_peek(IntcodeMachine *machine, size_t address, int64_t *value);
+
+/* Frees a machine created by `intcode_create`; passing NULL is a no-op. */
+void intcode_destroy(IntcodeMachine *machine);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* AOC19_INTCODE_H */
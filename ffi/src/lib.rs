@@ -0,0 +1,236 @@
+//! C interface to the Intcode interpreter.
+//!
+//! The matching header is generated from this file by `build.rs`; keep signatures to
+//! the handful of types it knows how to translate.
+
+use std::collections::VecDeque;
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::slice;
+
+use aoc19::intcode::{Command, Core, Op, Step};
+use aoc19::try_parse_ints;
+
+/// The machine executed an instruction and can keep going.
+pub const INTCODE_RUNNING: i32 = 0;
+/// The machine produced an output, available through `intcode_pop_output`.
+pub const INTCODE_OUTPUT: i32 = 1;
+/// The machine is waiting for `intcode_push_input`.
+pub const INTCODE_NEEDS_INPUT: i32 = 2;
+/// The program has exited.
+pub const INTCODE_HALTED: i32 = 3;
+/// No program is loaded, or it hit an invalid instruction or address.
+pub const INTCODE_ERROR: i32 = -1;
+
+/// Opaque handle to a machine along with its input and output queues.
+pub struct IntcodeMachine {
+    core: Option<Core<Vec<i64>>>,
+    inputs: VecDeque<i64>,
+    outputs: VecDeque<i64>,
+    halted: bool,
+}
+
+impl IntcodeMachine {
+    fn load(&mut self, data: Vec<i64>) {
+        self.core = Some(Core::new(data));
+        self.inputs.clear();
+        self.outputs.clear();
+        self.halted = false;
+    }
+
+    fn step(&mut self) -> i32 {
+        let core = match (&mut self.core, self.halted) {
+            (Some(core), false) => core,
+            (Some(_), true) => return INTCODE_HALTED,
+            (None, _) => return INTCODE_ERROR,
+        };
+        let input = self.inputs.front().cloned();
+        let opcode = core.data().get(core.pos()).cloned();
+        let is_input = opcode.and_then(Command::decode).map(|c| c.op) == Some(Op::Input);
+        let step = panic::catch_unwind(AssertUnwindSafe(|| core.step(input)));
+        match step {
+            Ok(Step::Running) => {
+                if is_input {
+                    self.inputs.pop_front();
+                }
+                INTCODE_RUNNING
+            }
+            Ok(Step::Output(value)) => {
+                self.outputs.push_back(value);
+                INTCODE_OUTPUT
+            }
+            Ok(Step::NeedsInput) => INTCODE_NEEDS_INPUT,
+            Ok(Step::Halted) => {
+                self.halted = true;
+                INTCODE_HALTED
+            }
+            Err(_) => {
+                self.core = None;
+                INTCODE_ERROR
+            }
+        }
+    }
+}
+
+/// Creates an empty machine; load a program before stepping it.
+#[no_mangle]
+pub extern "C" fn intcode_create() -> *mut IntcodeMachine {
+    let machine = IntcodeMachine {
+        core: None,
+        inputs: VecDeque::new(),
+        outputs: VecDeque::new(),
+        halted: false,
+    };
+    Box::into_raw(Box::new(machine))
+}
+
+/// Loads a copy of `len` cells at `data`, resetting the machine and its queues.
+///
+/// # Safety
+///
+/// `machine` must come from `intcode_create`, `data` must point to `len` values.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_load(
+    machine: *mut IntcodeMachine,
+    data: *const i64,
+    len: usize,
+) -> i32 {
+    if machine.is_null() || (data.is_null() && len != 0) {
+        return INTCODE_ERROR;
+    }
+    let data = if len == 0 {
+        Vec::new()
+    } else {
+        slice::from_raw_parts(data, len).to_vec()
+    };
+    (*machine).load(data);
+    INTCODE_RUNNING
+}
+
+/// Parses a comma-separated program and loads it like `intcode_load`.
+///
+/// # Safety
+///
+/// `machine` must come from `intcode_create`, `source` must be a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_load_text(
+    machine: *mut IntcodeMachine,
+    source: *const c_char,
+) -> i32 {
+    if machine.is_null() || source.is_null() {
+        return INTCODE_ERROR;
+    }
+    let source = match CStr::from_ptr(source).to_str() {
+        Ok(source) => source,
+        Err(_) => return INTCODE_ERROR,
+    };
+    match try_parse_ints(source, ',') {
+        Ok(data) => {
+            (*machine).load(data);
+            INTCODE_RUNNING
+        }
+        Err(_) => INTCODE_ERROR,
+    }
+}
+
+/// Executes a single instruction and returns one of the `INTCODE_*` statuses.
+///
+/// # Safety
+///
+/// `machine` must come from `intcode_create`.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_step(machine: *mut IntcodeMachine) -> i32 {
+    if machine.is_null() {
+        return INTCODE_ERROR;
+    }
+    (*machine).step()
+}
+
+/// Runs until the program exits, needs an input, or fails.
+///
+/// Returns `INTCODE_HALTED`, `INTCODE_NEEDS_INPUT` or `INTCODE_ERROR`; outputs produced
+/// along the way are queued.
+///
+/// # Safety
+///
+/// `machine` must come from `intcode_create`.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_run(machine: *mut IntcodeMachine) -> i32 {
+    loop {
+        match intcode_step(machine) {
+            INTCODE_RUNNING | INTCODE_OUTPUT => continue,
+            status => return status,
+        }
+    }
+}
+
+/// Queues a value to be consumed by the next input instruction.
+///
+/// # Safety
+///
+/// `machine` must come from `intcode_create`.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_push_input(machine: *mut IntcodeMachine, value: i64) {
+    if !machine.is_null() {
+        (*machine).inputs.push_back(value);
+    }
+}
+
+/// Takes the oldest queued output into `value`; returns 1 on success, 0 if none is left.
+///
+/// # Safety
+///
+/// `machine` must come from `intcode_create`, `value` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_pop_output(machine: *mut IntcodeMachine, value: *mut i64) -> i32 {
+    if machine.is_null() || value.is_null() {
+        return 0;
+    }
+    match (*machine).outputs.pop_front() {
+        Some(output) => {
+            *value = output;
+            1
+        }
+        None => 0,
+    }
+}
+
+/// Reads memory cell `address` into `value`; returns 1 on success, 0 if out of bounds.
+///
+/// # Safety
+///
+/// `machine` must come from `intcode_create`, `value` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_peek(
+    machine: *mut IntcodeMachine,
+    address: usize,
+    value: *mut i64,
+) -> i32 {
+    if machine.is_null() || value.is_null() {
+        return 0;
+    }
+    let cell = (*machine)
+        .core
+        .as_ref()
+        .and_then(|core| core.data().get(address));
+    match cell {
+        Some(&cell) => {
+            *value = cell;
+            1
+        }
+        None => 0,
+    }
+}
+
+/// Frees a machine created by `intcode_create`; passing NULL is a no-op.
+///
+/// # Safety
+///
+/// `machine` must come from `intcode_create` and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_destroy(machine: *mut IntcodeMachine) {
+    if !machine.is_null() {
+        drop(Box::from_raw(machine));
+    }
+}
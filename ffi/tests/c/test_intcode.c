@@ -0,0 +1,67 @@
+#include <stdio.h>
+#include <stdlib.h>
+
+#include "aoc19_intcode.h"
+
+#define CHECK(cond)                                                   \
+    do {                                                              \
+        if (!(cond)) {                                                \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__,    \
+                    __LINE__, #cond);                                 \
+            exit(1);                                                  \
+        }                                                             \
+    } while (0)
+
+/* Outputs 999, 1000 or 1001 depending on whether the input is below, equal or above 8. */
+static const int64_t COMPARE[] = {
+    3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0,
+    0, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4,
+    20, 1105, 1, 46, 98, 99,
+};
+
+static void test_compare(void) {
+    IntcodeMachine *machine = intcode_create();
+    int64_t input, output;
+    for (input = 6; input <= 10; input++) {
+        CHECK(intcode_load(machine, COMPARE, sizeof(COMPARE) / sizeof(COMPARE[0])) == INTCODE_RUNNING);
+        CHECK(intcode_run(machine) == INTCODE_NEEDS_INPUT);
+        intcode_push_input(machine, input);
+        CHECK(intcode_run(machine) == INTCODE_HALTED);
+        CHECK(intcode_pop_output(machine, &output) == 1);
+        CHECK(output == (input < 8 ? 999 : input == 8 ? 1000 : 1001));
+        CHECK(intcode_pop_output(machine, &output) == 0);
+    }
+    intcode_destroy(machine);
+}
+
+static void test_step_and_peek(void) {
+    IntcodeMachine *machine = intcode_create();
+    int64_t value;
+    CHECK(intcode_step(machine) == INTCODE_ERROR);
+    CHECK(intcode_load_text(machine, "1,9,10,3,2,3,11,0,99,30,40,50\n") == INTCODE_RUNNING);
+    CHECK(intcode_step(machine) == INTCODE_RUNNING);
+    CHECK(intcode_peek(machine, 3, &value) == 1 && value == 70);
+    CHECK(intcode_step(machine) == INTCODE_RUNNING);
+    CHECK(intcode_step(machine) == INTCODE_HALTED);
+    CHECK(intcode_step(machine) == INTCODE_HALTED);
+    CHECK(intcode_peek(machine, 0, &value) == 1 && value == 3500);
+    CHECK(intcode_peek(machine, 12, &value) == 0);
+    intcode_destroy(machine);
+}
+
+static void test_errors(void) {
+    IntcodeMachine *machine = intcode_create();
+    CHECK(intcode_load_text(machine, "42") == INTCODE_RUNNING);
+    CHECK(intcode_run(machine) == INTCODE_ERROR);
+    CHECK(intcode_load_text(machine, "1,2,x") == INTCODE_ERROR);
+    intcode_destroy(machine);
+    intcode_destroy(NULL);
+}
+
+int main(void) {
+    test_compare();
+    test_step_and_peek();
+    test_errors();
+    printf("ok\n");
+    return 0;
+}
@@ -0,0 +1,50 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const HEADER: &str = include_str!(concat!(env!("OUT_DIR"), "/aoc19_intcode.h"));
+
+fn deps_dir() -> PathBuf {
+    // tests run from target/<profile>/deps, which is also where the cdylib is built
+    env::current_exe().unwrap().parent().unwrap().to_path_buf()
+}
+
+#[test]
+fn test_header_up_to_date() {
+    assert_eq!(
+        include_str!("../include/aoc19_intcode.h"),
+        HEADER,
+        "stale header, copy it from {}",
+        env!("OUT_DIR")
+    );
+}
+
+#[test]
+fn test_c_program() {
+    let deps = deps_dir();
+    let exe = deps.join("test_intcode_c");
+    let source = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/c/test_intcode.c");
+    let cc = env::var("CC").unwrap_or_else(|_| "cc".into());
+    let status = Command::new(cc)
+        .arg(&source)
+        .arg("-std=c99")
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg(format!("-I{}", env!("OUT_DIR")))
+        .arg(format!("-L{}", deps.display()))
+        .arg(format!("-Wl,-rpath,{}", deps.display()))
+        .arg("-laoc19_ffi")
+        .arg("-o")
+        .arg(&exe)
+        .status()
+        .expect("failed to run the C compiler");
+    assert!(status.success(), "failed to compile {}", source.display());
+
+    let output = Command::new(&exe).output().unwrap();
+    assert!(
+        output.status.success(),
+        "C test failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "ok\n");
+}
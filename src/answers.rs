@@ -0,0 +1,85 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::input::profile_path;
+
+/// A known answer for one part of a day, computed from a given input set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Expected {
+    pub day: u32,
+    pub part: u32,
+    pub set: String,
+    pub answer: String,
+}
+
+impl Expected {
+    /// Path of the input for this entry relative to an inputs directory; the set
+    /// is the name of the profile the input belongs to.
+    pub fn input_path(&self) -> PathBuf {
+        profile_path(&self.set, self.day)
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match (c, c == '\\') {
+            (_, true) => match chars.next() {
+                Some('n') => out.push('\n'),
+                Some(c) => out.push(c),
+                None => out.push('\\'),
+            },
+            (c, false) => out.push(c),
+        }
+    }
+    out
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let answer = escape(&self.answer);
+        write!(f, "{} {} {} {}", self.day, self.part, self.set, answer)
+    }
+}
+
+/// Parses a manifest of `<day> <part> <set> <answer>` lines; the answer runs to the end
+/// of the line with `\n` standing for a newline. Blank lines and `#` comments are skipped.
+pub fn parse_answers(text: &str) -> Result<Vec<Expected>, String> {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim_start()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(lineno, line)| {
+            let fields: Vec<_> = line.splitn(4, ' ').collect();
+            let error = |what: &str| format!("line {}: {}: {:?}", lineno, what, line);
+            if fields.len() != 4 {
+                return Err(error("expected `<day> <part> <set> <answer>`"));
+            }
+            Ok(Expected {
+                day: fields[0].parse().map_err(|_| error("invalid day"))?,
+                part: fields[1].parse().map_err(|_| error("invalid part"))?,
+                set: fields[2].into(),
+                answer: unescape(fields[3]),
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn test_parse_answers() {
+    use std::path::Path;
+
+    let text = "# comment\n\n1 2 default 42\n8 2 alice a\\nb \\\\ c\n";
+    let answers = parse_answers(text).unwrap();
+    assert_eq!(answers.len(), 2);
+    assert_eq!(answers[0].input_path(), Path::new("day-01.txt"));
+    assert_eq!(answers[1].input_path(), Path::new("alice/day-08.txt"));
+    assert_eq!(answers[1].answer, "a\nb \\ c");
+    assert_eq!(answers[1].to_string(), "8 2 alice a\\nb \\\\ c");
+    assert!(parse_answers("1 x default 1").is_err());
+}
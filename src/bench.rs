@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use crate::days::{Day, Run};
+use crate::ParseError;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Self {
+        let mut samples = samples.to_vec();
+        samples.sort();
+        let n = samples.len();
+        let median = if n % 2 == 1 {
+            samples[n / 2]
+        } else {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        };
+        Self {
+            min: samples[0],
+            median,
+            max: samples[n - 1],
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bench {
+    pub repeat: usize,
+    pub parse: Stats,
+    pub parts: Vec<(u32, Stats)>,
+    pub total: Stats,
+}
+
+/// Solves a day `repeat` times over the same input and summarizes the timings.
+pub fn bench(day: &Day, input: &str, parts: &[u32], repeat: usize) -> Result<Bench, ParseError> {
+    assert!(repeat > 0, "need at least one run");
+    let runs = (0..repeat)
+        .map(|_| (day.solve)(input, parts))
+        .collect::<Result<Vec<Run>, _>>()?;
+    let stats = |f: &dyn Fn(&Run) -> Duration| Stats::new(&runs.iter().map(f).collect::<Vec<_>>());
+    let parts = runs[0]
+        .parts
+        .iter()
+        .map(|p| (p.part, stats(&|run| run.elapsed(p.part).unwrap())))
+        .collect();
+    Ok(Bench {
+        repeat,
+        parse: stats(&|run| run.parse),
+        parts,
+        total: stats(&Run::total),
+    })
+}
+
+/// Formats a duration with three significant digits in the most fitting unit.
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+    let (value, unit) = if nanos < 1e3 {
+        (nanos, "ns")
+    } else if nanos < 1e6 {
+        (nanos / 1e3, "µs")
+    } else if nanos < 1e9 {
+        (nanos / 1e6, "ms")
+    } else {
+        (nanos / 1e9, "s")
+    };
+    let precision = if value < 10. {
+        2
+    } else if value < 100. {
+        1
+    } else {
+        0
+    };
+    format!("{:.*}{}", precision, value, unit)
+}
+
+#[test]
+fn test_stats() {
+    let ms = Duration::from_millis;
+    let stats = Stats::new(&[ms(5), ms(1), ms(3)]);
+    assert_eq!((stats.min, stats.median, stats.max), (ms(1), ms(3), ms(5)));
+    assert_eq!(Stats::new(&[ms(4), ms(2)]).median, ms(3));
+}
+
+#[test]
+fn test_format_duration() {
+    assert_eq!(format_duration(Duration::from_nanos(512)), "512ns");
+    assert_eq!(format_duration(Duration::from_nanos(12_345)), "12.3µs");
+    assert_eq!(format_duration(Duration::from_micros(1_500)), "1.50ms");
+    assert_eq!(format_duration(Duration::from_millis(2_345)), "2.35s");
+}
@@ -0,0 +1,211 @@
+//! A small unsigned big integer, with just the arithmetic the puzzles need.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::{self, Display};
+use core::ops::{Add, AddAssign};
+use core::str::FromStr;
+
+const BASE: u32 = 1_000_000_000;
+const BASE_DIGITS: usize = 9;
+
+/// An arbitrarily large non-negative integer, stored as base 10^9 limbs starting from
+/// the least significant one, with no zero limbs at the top.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    fn normalize(mut self) -> Self {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        self
+    }
+
+    /// Quotient and remainder of division by a small non-zero number.
+    pub fn div_rem_small(&self, divisor: u32) -> (Self, u32) {
+        assert!(divisor != 0, "division by zero");
+        let mut limbs = self.limbs.clone();
+        let mut rem = 0u64;
+        for limb in limbs.iter_mut().rev() {
+            let cur = rem * BASE as u64 + *limb as u64;
+            *limb = (cur / divisor as u64) as u32;
+            rem = cur % divisor as u64;
+        }
+        (Self { limbs }.normalize(), rem as u32)
+    }
+
+    /// The difference with a small number, or `None` if it would be negative.
+    pub fn checked_sub_small(&self, value: u32) -> Option<Self> {
+        if *self < Self::from(value as u128) {
+            return None;
+        }
+        let mut limbs = self.limbs.clone();
+        let mut borrow = value as i64;
+        for limb in limbs.iter_mut() {
+            if borrow == 0 {
+                break;
+            }
+            let cur = *limb as i64 - borrow;
+            borrow = 0;
+            *limb = if cur < 0 {
+                borrow = (-cur + BASE as i64 - 1) / BASE as i64;
+                (cur + borrow * BASE as i64) as u32
+            } else {
+                cur as u32
+            };
+        }
+        Some(Self { limbs }.normalize())
+    }
+
+    /// The value if it fits in a `u128`.
+    pub fn to_u128(&self) -> Option<u128> {
+        self.limbs.iter().rev().try_fold(0u128, |acc, &limb| {
+            acc.checked_mul(BASE as u128)?.checked_add(limb as u128)
+        })
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(mut value: u128) -> Self {
+        let mut limbs = Vec::new();
+        while value > 0 {
+            limbs.push((value % BASE as u128) as u32);
+            value /= BASE as u128;
+        }
+        Self { limbs }
+    }
+}
+
+impl<'a> Add<&'a BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let mut sum = self.clone();
+        sum += other;
+        sum
+    }
+}
+
+impl Add for BigUint {
+    type Output = BigUint;
+
+    fn add(mut self, other: BigUint) -> BigUint {
+        self += &other;
+        self
+    }
+}
+
+impl AddAssign<&BigUint> for BigUint {
+    fn add_assign(&mut self, other: &BigUint) {
+        if self.limbs.len() < other.limbs.len() {
+            self.limbs.resize(other.limbs.len(), 0);
+        }
+        let mut carry = 0;
+        for (i, limb) in self.limbs.iter_mut().enumerate() {
+            let cur = *limb + other.limbs.get(i).cloned().unwrap_or(0) + carry;
+            carry = (cur >= BASE) as u32;
+            *limb = cur - carry * BASE;
+            if carry == 0 && i >= other.limbs.len() {
+                break;
+            }
+        }
+        if carry > 0 {
+            self.limbs.push(carry);
+        }
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseBigUintError;
+
+impl Display for ParseBigUintError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected decimal digits")
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = ParseBigUintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_prefix('+').unwrap_or(s);
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseBigUintError);
+        }
+        let limbs = s
+            .as_bytes()
+            .rchunks(BASE_DIGITS)
+            .map(|chunk| chunk.iter().fold(0, |acc, &b| acc * 10 + (b - b'0') as u32))
+            .collect();
+        Ok(Self { limbs }.normalize())
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut limbs = self.limbs.iter().rev();
+        let mut s = match limbs.next() {
+            Some(top) => alloc::format!("{}", top),
+            None => String::from("0"),
+        };
+        for limb in limbs {
+            s.push_str(&alloc::format!("{:09}", limb));
+        }
+        f.pad_integral(true, "", &s)
+    }
+}
+
+#[test]
+fn test_biguint() {
+    use alloc::format;
+    use alloc::string::ToString;
+
+    let big: BigUint = "123456789012345678901234567890".parse().unwrap();
+    assert_eq!(big.to_string(), "123456789012345678901234567890");
+    assert_eq!(big.to_u128(), Some(123456789012345678901234567890));
+    assert_eq!("000".parse::<BigUint>().unwrap(), BigUint::zero());
+    assert_eq!(BigUint::zero().to_string(), "0");
+    assert!("12a".parse::<BigUint>().is_err() && "".parse::<BigUint>().is_err());
+
+    let max = BigUint::from(u128::MAX);
+    let sum = &max + &max;
+    assert_eq!(sum.to_string(), "680564733841876926926749214863536422910");
+    assert_eq!(sum.to_u128(), None);
+    assert_eq!(sum.div_rem_small(2), (max.clone(), 0));
+    let (q, r) = BigUint::from(1_000_000_000_000_000_005).div_rem_small(3);
+    assert_eq!((q, r), (BigUint::from(333_333_333_333_333_335), 0));
+    assert_eq!(
+        BigUint::from(1_000_000_000).checked_sub_small(2),
+        Some(BigUint::from(999_999_998))
+    );
+    assert_eq!(BigUint::from(1).checked_sub_small(2), None);
+    assert!(BigUint::from(10) < BigUint::from(1_000_000_000) && sum > max);
+    assert_eq!(format!("{:>5}", BigUint::from(42)), "   42");
+}
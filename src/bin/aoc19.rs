@@ -0,0 +1,361 @@
+use std::env;
+use std::io;
+use std::process;
+use std::time::Duration;
+
+use aoc19::bench::{bench, format_duration, Stats};
+use aoc19::days::{get_day, Day, PartRun, Run, DAYS};
+use aoc19::{
+    current_profile, input_dir, load_input, parse_answers, profile_path, profiles, Answer,
+    Expected, Input, Json, Source, EXAMPLE_PROFILE,
+};
+
+const USAGE: &str = "\
+usage: aoc19 <command> [options]
+
+commands:
+    list                    list the available days, their inputs and the profiles
+    run                     run one or all days
+    bench                   run days repeatedly and report timing statistics
+    compare                 run days for every profile and check the known answers
+
+options for `run`, `bench` and `compare`:
+    -d, --day <day>         day to run
+    -p, --part <part>       part to run (default: both)
+    -a, --all               run every day with an input for the profile
+    -i, --input <path>      input file for a single day, `-` for stdin
+    -P, --profile <name>    input profile (default: $AOC19_PROFILE or `default`)
+    -t, --time              print a timing summary after the answers
+    -j, --json              print the answers as a JSON array (`run` only)
+    -r, --repeat <n>        number of runs for `bench` (default: 10)
+    -h, --help              print this message
+
+Inputs are read from $AOC19_INPUTS (default: `inputs`): `day-NN.txt` for the default
+profile and `<profile>/day-NN.txt` for the others, falling back to the bundled copies
+for the default and `example` profiles. Known answers for `compare` are read from
+`answers.txt` there, as `<day> <part> <profile> <answer>` lines.";
+
+#[derive(Debug, Default)]
+struct Options {
+    days: Vec<u32>,
+    parts: Vec<u32>,
+    input: Option<String>,
+    profile: String,
+    /// Set by `--all`: days without an input for the profile are skipped.
+    all: bool,
+    time: bool,
+    json: bool,
+    repeat: usize,
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("aoc19: {}", message);
+    process::exit(2);
+}
+
+fn parse_value<T: std::str::FromStr>(s: &str, what: &str) -> T {
+    s.parse()
+        .unwrap_or_else(|_| fail(&format!("invalid {}: {:?}", what, s)))
+}
+
+fn parse_run_args(args: impl Iterator<Item = String>) -> Options {
+    let mut options = Options {
+        repeat: 10,
+        profile: current_profile(),
+        ..Options::default()
+    };
+    let mut args = args.peekable();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .unwrap_or_else(|| fail(&format!("missing value for {}", name)))
+        };
+        match arg.as_str() {
+            "-d" | "--day" => options.days.push(parse_value(&value(&arg), "day")),
+            "-p" | "--part" => options.parts.push(parse_value(&value(&arg), "part")),
+            "-a" | "--all" => options.all = true,
+            "-i" | "--input" => options.input = Some(value(&arg)),
+            "-P" | "--profile" => options.profile = value(&arg),
+            "-t" | "--time" => options.time = true,
+            "-j" | "--json" => options.json = true,
+            "-r" | "--repeat" => options.repeat = parse_value(&value(&arg), "repeat count"),
+            _ => fail(&format!("unexpected argument {}", arg)),
+        }
+    }
+    if options.all {
+        options.days = DAYS.iter().map(|day| day.day).collect();
+    }
+    if options.days.is_empty() {
+        fail("expected --day or --all");
+    }
+    if options.input.is_some() && options.days.len() > 1 {
+        fail("--input can only be used with a single day");
+    }
+    if options.json && options.time {
+        fail("--time cannot be combined with --json, which already includes timings");
+    }
+    if options.repeat == 0 {
+        fail("--repeat must be positive");
+    }
+    if options.parts.is_empty() {
+        options.parts = vec![1, 2];
+    }
+    options
+}
+
+fn print_answer(day: &Day, part: u32, answer: &Answer) {
+    let answer = answer.to_string();
+    if answer.contains('\n') {
+        println!("day {:02} part {}:\n{}", day.day, part, answer);
+    } else {
+        println!("day {:02} part {}: {}", day.day, part, answer);
+    }
+}
+
+/// `None` for days skipped by `--all` because the profile has no input for them.
+fn load_day(day: u32, options: &Options) -> Option<(&'static Day, Input)> {
+    let day = get_day(day).unwrap_or_else(|| fail(&format!("day {} is not available", day)));
+    let profile = &options.profile;
+    let input = load_input(
+        day.day,
+        profile,
+        options.input.as_deref(),
+        day.bundled_for(profile),
+    );
+    match input {
+        Ok(input) => Some((day, input)),
+        Err(err) if options.all && err.kind() == io::ErrorKind::NotFound => {
+            eprintln!("aoc19: skipping day {:02}: {}", day.day, err);
+            None
+        }
+        Err(err) => fail(&format!("cannot read input: {}", err)),
+    }
+}
+
+fn parse_failed(input: &Input, err: impl std::fmt::Display) -> ! {
+    eprintln!("aoc19: cannot parse {}: {}", input.source, err);
+    process::exit(1);
+}
+
+fn print_timings(runs: &[(u32, Run)]) {
+    let cell = |duration: Option<Duration>| duration.map_or("-".into(), format_duration);
+    println!();
+    println!(
+        "{:>3}  {:>9}  {:>9}  {:>9}  {:>9}",
+        "day", "parse", "part 1", "part 2", "total"
+    );
+    for (day, run) in runs {
+        println!(
+            "{:>3}  {:>9}  {:>9}  {:>9}  {:>9}",
+            format!("{:02}", day),
+            cell(Some(run.parse)),
+            cell(run.elapsed(1)),
+            cell(run.elapsed(2)),
+            cell(Some(run.total())),
+        );
+    }
+    if runs.len() > 1 {
+        let total = runs.iter().map(|(_, run)| run.total()).sum();
+        println!("{:>3}  {:>42}", "all", format_duration(total));
+    }
+}
+
+fn json_record(day: &Day, part: &PartRun, input: &Input, options: &Options) -> Json {
+    let mut fields = vec![
+        ("day", day.day.into()),
+        ("part", part.part.into()),
+        ("answer", part.answer.to_json()),
+        ("elapsed_ns", (part.elapsed.as_nanos() as i64).into()),
+        ("input", input.source.to_string().into()),
+        ("profile", options.profile.as_str().into()),
+    ];
+    if let Answer::Image(rows) = &part.answer {
+        fields.push(("letters", part.answer.letters().into()));
+        let rows = rows.iter().map(|row| row.as_str().into()).collect();
+        fields.push(("rows", Json::Array(rows)));
+    }
+    Json::object(fields)
+}
+
+fn run(options: &Options) {
+    let mut runs = Vec::new();
+    let mut records = Vec::new();
+    for &day in &options.days {
+        let (day, input) = match load_day(day, options) {
+            Some(loaded) => loaded,
+            None => continue,
+        };
+        let run = (day.solve)(&input.text, &options.parts)
+            .unwrap_or_else(|err| parse_failed(&input, err));
+        for p in &run.parts {
+            if options.json {
+                records.push(json_record(day, p, &input, options));
+            } else {
+                print_answer(day, p.part, &p.answer);
+            }
+        }
+        runs.push((day.day, run));
+    }
+    if options.json {
+        println!("{}", Json::Array(records));
+    }
+    if options.time {
+        print_timings(&runs);
+    }
+}
+
+fn run_bench(options: &Options) {
+    if options.json {
+        fail("--json is only supported by `run`");
+    }
+    let row = |name: &str, stats: &Stats| {
+        println!(
+            "  {:<8}  {:>9}  {:>9}  {:>9}",
+            name,
+            format_duration(stats.min),
+            format_duration(stats.median),
+            format_duration(stats.max),
+        );
+    };
+    for &day in &options.days {
+        let (day, input) = match load_day(day, options) {
+            Some(loaded) => loaded,
+            None => continue,
+        };
+        let result = bench(day, &input.text, &options.parts, options.repeat)
+            .unwrap_or_else(|err| parse_failed(&input, err));
+        println!("day {:02}, {} runs", day.day, result.repeat);
+        println!("  {:<8}  {:>9}  {:>9}  {:>9}", "", "min", "median", "max");
+        row("parse", &result.parse);
+        for (part, stats) in &result.parts {
+            row(&format!("part {}", part), stats);
+        }
+        row("total", &result.total);
+    }
+}
+
+fn list_profiles() -> Vec<String> {
+    let mut profiles =
+        profiles().unwrap_or_else(|err| fail(&format!("cannot list inputs: {}", err)));
+    if !profiles.iter().any(|profile| profile == EXAMPLE_PROFILE) {
+        profiles.push(EXAMPLE_PROFILE.into());
+    }
+    profiles
+}
+
+fn list() {
+    let profile = current_profile();
+    for day in DAYS {
+        let path = input_dir().join(profile_path(&profile, day.day));
+        let source = match day.bundled_for(&profile) {
+            _ if path.is_file() => Source::Path(path).to_string(),
+            Some(_) => Source::Bundled.to_string(),
+            None => "-".into(),
+        };
+        println!("day {:02}  {}", day.day, source);
+    }
+    println!();
+    println!("profiles: {}", list_profiles().join(", "));
+}
+
+/// Known answers from the input directory's manifest, and from the bundled one for
+/// inputs that weren't found in the input directory.
+fn load_expected() -> (Vec<Expected>, Vec<Expected>) {
+    let path = input_dir().join("answers.txt");
+    let local = if path.is_file() {
+        let text = std::fs::read_to_string(&path)
+            .unwrap_or_else(|err| fail(&format!("cannot read {}: {}", path.display(), err)));
+        parse_answers(&text)
+            .unwrap_or_else(|err| fail(&format!("cannot parse {}: {}", path.display(), err)))
+    } else {
+        Vec::new()
+    };
+    let bundled = parse_answers(include_str!("inputs/answers.txt")).unwrap();
+    (local, bundled)
+}
+
+fn summary(answer: &Answer) -> String {
+    answer
+        .letters()
+        .unwrap_or_else(|| answer.to_string().replace('\n', "\\n"))
+}
+
+fn compare(options: &Options) {
+    if options.input.is_some() {
+        fail("--input cannot be used with `compare`");
+    }
+    let (local, bundled) = load_expected();
+    let find = |expected: &[Expected], profile: &str, day: u32, part: u32| {
+        expected
+            .iter()
+            .find(|e| e.set == profile && e.day == day && e.part == part)
+            .map(|e| e.answer.clone())
+    };
+    let profiles = list_profiles();
+    let width = profiles.iter().map(String::len).max().unwrap_or(0);
+    let mut failed = false;
+    for &day in &options.days {
+        let day = get_day(day).unwrap_or_else(|| fail(&format!("day {} is not available", day)));
+        let mut rows = Vec::new();
+        for profile in &profiles {
+            let input = match load_input(day.day, profile, None, day.bundled_for(profile)) {
+                Ok(input) => input,
+                Err(_) => continue,
+            };
+            let run = match (day.solve)(&input.text, &options.parts) {
+                Ok(run) => run,
+                Err(err) => {
+                    failed = true;
+                    let status = format!("cannot parse: {}", err);
+                    for &part in &options.parts {
+                        rows.push((part, profile, "-".into(), status.clone()));
+                    }
+                    continue;
+                }
+            };
+            let manifest = match input.source {
+                Source::Bundled => &bundled,
+                _ => &local,
+            };
+            for p in run.parts {
+                let status = match find(manifest, profile, day.day, p.part) {
+                    None => "unchecked".to_string(),
+                    Some(expected) if expected == p.answer.to_string() => "ok".to_string(),
+                    Some(expected) => {
+                        failed = true;
+                        format!("MISMATCH, expected {}", expected.replace('\n', "\\n"))
+                    }
+                };
+                rows.push((p.part, profile, summary(&p.answer), status));
+            }
+        }
+        rows.sort_by_key(|row| row.0);
+        for (part, profile, answer, status) in rows {
+            println!(
+                "day {:02} part {}  {:<w$}  {:<16}  {}",
+                day.day,
+                part,
+                profile,
+                answer,
+                status,
+                w = width
+            );
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn main() {
+    let mut args = env::args().skip(1);
+    match args.next().as_deref() {
+        Some("list") => list(),
+        Some("run") => run(&parse_run_args(args)),
+        Some("bench") => run_bench(&parse_run_args(args)),
+        Some("compare") => compare(&parse_run_args(args)),
+        Some("-h") | Some("--help") => println!("{}", USAGE),
+        Some(command) => fail(&format!("unknown command {}\n\n{}", command, USAGE)),
+        None => fail(USAGE),
+    }
+}
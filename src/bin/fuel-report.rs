@@ -0,0 +1,7 @@
+use aoc19::days::day01::{fuel_report, to_csv, Day01};
+
+fn main() {
+    aoc19::days::tool::<Day01>("fuel-report", 1, "csv", |modules| {
+        to_csv(&fuel_report(modules))
+    });
+}
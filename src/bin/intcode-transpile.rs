@@ -0,0 +1,30 @@
+use std::env;
+use std::fs;
+use std::process;
+
+use aoc19::intcode::transpile;
+use aoc19::try_parse_ints;
+
+fn main() {
+    let args: Vec<_> = env::args().skip(1).collect();
+    if args.is_empty() || args.len() > 2 {
+        eprintln!("usage: intcode-transpile <program> [output.rs]");
+        process::exit(2);
+    }
+    let input = fs::read_to_string(&args[0]).unwrap_or_else(|err| {
+        eprintln!("cannot read {}: {}", args[0], err);
+        process::exit(1);
+    });
+    let data: Vec<i64> = try_parse_ints(&input, ',').unwrap_or_else(|err| {
+        eprintln!("cannot parse {}: {}", args[0], err);
+        process::exit(1);
+    });
+    let code = transpile(&data);
+    match args.get(1) {
+        Some(path) => fs::write(path, code).unwrap_or_else(|err| {
+            eprintln!("cannot write {}: {}", path, err);
+            process::exit(1);
+        }),
+        None => print!("{}", code),
+    }
+}
@@ -0,0 +1,139 @@
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Read, Write};
+use std::process;
+
+use aoc19::intcode::Machine;
+use aoc19::try_parse_ints;
+
+const USAGE: &str = "\
+usage: intcode [options] [program]
+
+Runs an Intcode program read from a file, or from stdin if no path (or `-`) is given.
+When the program asks for more input than was passed on the command line, values are
+read interactively from stdin.
+
+options:
+    -i, --input <values>    comma-separated input values, may be repeated
+    -s, --set <addr=value>  patch memory before running, may be repeated
+    -m, --memory            print final memory after the program exits
+    -h, --help              print this message";
+
+#[derive(Debug, Default)]
+struct Options {
+    program: Option<String>,
+    inputs: Vec<i64>,
+    patches: Vec<(usize, i64)>,
+    memory: bool,
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("intcode: {}", message);
+    process::exit(2);
+}
+
+fn parse_value<T: std::str::FromStr>(s: &str, what: &str) -> T {
+    s.trim()
+        .parse()
+        .unwrap_or_else(|_| fail(&format!("invalid {}: {:?}", what, s)))
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Options {
+    let mut options = Options::default();
+    let mut args = args.peekable();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .unwrap_or_else(|| fail(&format!("missing value for {}", name)))
+        };
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            "-i" | "--input" => {
+                let values = value(&arg);
+                let values = values.split(',').map(|s| parse_value::<i64>(s, "input"));
+                options.inputs.extend(values);
+            }
+            "-s" | "--set" => {
+                let patch = value(&arg);
+                let idx = patch
+                    .find('=')
+                    .unwrap_or_else(|| fail(&format!("expected addr=value, got {:?}", patch)));
+                let address = parse_value(&patch[..idx], "address");
+                let value = parse_value(&patch[idx + 1..], "value");
+                options.patches.push((address, value));
+            }
+            "-m" | "--memory" => options.memory = true,
+            _ if arg.starts_with('-') && arg != "-" => fail(&format!("unknown option {}", arg)),
+            _ if options.program.is_some() => fail("more than one program given"),
+            _ => options.program = Some(arg),
+        }
+    }
+    options
+}
+
+fn load_program(path: Option<&str>) -> Vec<i64> {
+    let mut source = String::new();
+    match path {
+        Some(path) if path != "-" => {
+            source = fs::read_to_string(path)
+                .unwrap_or_else(|err| fail(&format!("cannot read {}: {}", path, err)));
+        }
+        _ => {
+            io::stdin()
+                .read_to_string(&mut source)
+                .unwrap_or_else(|err| fail(&format!("cannot read stdin: {}", err)));
+        }
+    }
+    try_parse_ints(&source, ',')
+        .unwrap_or_else(|err| fail(&format!("cannot parse program: {}", err)))
+}
+
+fn prompt_input() -> i64 {
+    eprint!("input> ");
+    io::stderr().flush().unwrap();
+    let mut line = String::new();
+    match io::stdin().lock().read_line(&mut line) {
+        Ok(0) => fail("program needs more input, but stdin is closed"),
+        Ok(_) => parse_value(&line, "input"),
+        Err(err) => fail(&format!("cannot read input: {}", err)),
+    }
+}
+
+fn main() {
+    let options = parse_args(env::args().skip(1));
+    let mut data = load_program(options.program.as_deref());
+    for &(address, value) in &options.patches {
+        if address >= data.len() {
+            fail(&format!("address {} is out of bounds", address));
+        }
+        data[address] = value;
+    }
+
+    let mut machine = Machine::new(&data, &options.inputs);
+    let mut n_printed = 0;
+    loop {
+        if machine.needs_input() {
+            machine.push_input(prompt_input());
+            continue;
+        }
+        if !machine.can_step() {
+            fail(&format!("invalid instruction at {}", machine.pos()));
+        }
+        let running = machine.step();
+        machine.outputs()[n_printed..]
+            .iter()
+            .for_each(|output| println!("{}", output));
+        n_printed = machine.outputs().len();
+        if !running {
+            break;
+        }
+    }
+
+    if options.memory {
+        let memory: Vec<_> = machine.data().iter().map(ToString::to_string).collect();
+        println!("{}", memory.join(","));
+    }
+}
@@ -0,0 +1,5 @@
+use aoc19::days::day03::{to_svg, Day03};
+
+fn main() {
+    aoc19::days::tool::<Day03>("wires-svg", 3, "svg", |wires| to_svg(&wires.wires));
+}
@@ -0,0 +1,368 @@
+use std::fmt;
+use std::iter;
+
+use crate::bigint::BigUint;
+use crate::{tokenize, Answer, ParseError, ParseOptions, Solution};
+
+/// How much fuel a module needs for its mass.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FuelModel {
+    /// Fuel for the module alone, as in part 1.
+    Simple,
+    /// Fuel for the module and for the fuel itself, as in part 2.
+    Recursive,
+}
+
+impl FuelModel {
+    pub fn fuel(self, mass: i64) -> i64 {
+        match self {
+            FuelModel::Simple => mass / 3 - 2,
+            FuelModel::Recursive => compute_fuel(mass),
+        }
+    }
+
+    /// The largest mass whose fuel fits in `budget`, if any mass does; fuel never
+    /// decreases as the mass grows, so this is a binary search.
+    pub fn max_mass(self, budget: i64) -> Option<i64> {
+        if self.fuel(0) > budget {
+            return None;
+        }
+        // fuel is at least a third of the mass minus 2 past the smallest masses
+        let (mut lo, mut hi) = (0, budget.saturating_add(3).saturating_mul(3));
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2 + 1;
+            if self.fuel(mid) <= budget {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        Some(lo)
+    }
+}
+
+fn fuel_sequence(mass: i64) -> impl Iterator<Item = i64> {
+    iter::successors(Some(mass), |&f| if f > 6 { Some(f / 3 - 2) } else { None }).skip(1)
+}
+
+fn compute_fuel(mass: i64) -> i64 {
+    fuel_sequence(mass).sum()
+}
+
+/// Number of times fuel is added for a module when accounting for the fuel's own mass.
+fn fuel_steps(mass: i64) -> usize {
+    fuel_sequence(mass).filter(|&fuel| fuel > 0).count()
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Module {
+    pub label: Option<String>,
+    pub mass: i64,
+}
+
+/// Reads one module per line, as a mass optionally preceded by a label: `1969` or
+/// `main engine 1969`.
+fn parse_modules(input: &str) -> Result<Vec<Module>, ParseError> {
+    tokenize(input, '\n', ParseOptions::default())
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(offset, line)| {
+            let (label, mass) = match line.rsplit_once(char::is_whitespace) {
+                Some((label, mass)) => (Some(label.trim_end().to_string()), mass),
+                None => (None, line),
+            };
+            let mass_offset = offset + line.len() - mass.len();
+            let mass = mass
+                .parse()
+                .map_err(|err| ParseError::new(input, mass_offset, mass, err))?;
+            Ok(Module { label, mass })
+        })
+        .collect()
+}
+
+/// Fuel needed by a single module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModuleFuel {
+    pub label: Option<String>,
+    pub mass: i64,
+    pub fuel: i64,
+    pub total_fuel: i64,
+    pub steps: usize,
+}
+
+pub fn fuel_report(modules: &[Module]) -> Vec<ModuleFuel> {
+    modules
+        .iter()
+        .map(|module| ModuleFuel {
+            label: module.label.clone(),
+            mass: module.mass,
+            fuel: FuelModel::Simple.fuel(module.mass),
+            total_fuel: FuelModel::Recursive.fuel(module.mass),
+            steps: fuel_steps(module.mass),
+        })
+        .collect()
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.into()
+    }
+}
+
+/// The report as CSV with a header row; unlabelled modules have an empty label.
+pub fn to_csv(report: &[ModuleFuel]) -> String {
+    let mut csv = String::from("label,mass,fuel,total_fuel,steps\n");
+    for row in report {
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            csv_field(row.label.as_deref().unwrap_or("")),
+            row.mass,
+            row.fuel,
+            row.total_fuel,
+            row.steps
+        ));
+    }
+    csv
+}
+
+/// A running total of fuel that no longer fits in an `i64`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OverflowError {
+    /// Index of the module whose fuel didn't fit.
+    pub module: usize,
+}
+
+impl fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "fuel total overflows at module {}", self.module + 1)
+    }
+}
+
+impl std::error::Error for OverflowError {}
+
+/// Total fuel for all the modules, failing instead of wrapping around.
+pub fn checked_total(modules: &[Module], model: FuelModel) -> Result<i64, OverflowError> {
+    modules.iter().enumerate().try_fold(0i64, |total, (i, m)| {
+        total
+            .checked_add(model.fuel(m.mass))
+            .ok_or(OverflowError { module: i })
+    })
+}
+
+/// Fuel for masses too large for an `i64`. Unlike the signed version, masses below 6
+/// take no fuel rather than a negative amount.
+pub fn fuel_u128(model: FuelModel, mass: u128) -> u128 {
+    match model {
+        FuelModel::Simple => (mass / 3).saturating_sub(2),
+        FuelModel::Recursive => fast_recursive_fuel(mass),
+    }
+}
+
+/// Recursive fuel without stepping through every intermediate amount of fuel.
+///
+/// With `n = mass + 3`, the `k`-th amount of fuel added is `n / 3^k - 3` (rounding
+/// down), and it's added as long as `n >= 10 * 3^(k - 1)`. Summing `n / 3^k` over all
+/// `k >= 1` gives `(n - s)/2` where `s` is the sum of the base-3 digits of `n`, so the
+/// total is that, minus the terms past the last added one, minus 3 per added term.
+pub fn fast_recursive_fuel(mass: u128) -> u128 {
+    let n = match mass.checked_add(3) {
+        Some(n) if n < 10 => return 0,
+        Some(n) => n,
+        // too close to the limit to add 3, so take the first step by hand
+        None => {
+            let fuel = mass / 3 - 2;
+            return fuel + fast_recursive_fuel(fuel);
+        }
+    };
+    let mut digit_sum = 0;
+    let mut rest = n;
+    while rest > 0 {
+        digit_sum += rest % 3;
+        rest /= 3;
+    }
+    // `terms` counts the added amounts, `power` ends up as 3^terms
+    let (mut terms, mut power) = (0, 1u128);
+    while n / power >= 10 {
+        terms += 1;
+        power *= 3;
+    }
+    let mut tail = 0;
+    while power <= n / 3 {
+        power *= 3;
+        tail += n / power;
+    }
+    (n - digit_sum) / 2 - tail - 3 * terms
+}
+
+#[cfg(test)]
+fn fuel_sequence_u128(mass: u128) -> u128 {
+    iter::successors(Some(mass), |&f| if f > 6 { Some(f / 3 - 2) } else { None })
+        .skip(1)
+        .sum()
+}
+
+/// Fuel for masses of any size.
+pub fn fuel_big(model: FuelModel, mass: &BigUint) -> BigUint {
+    let step = |mass: &BigUint| mass.div_rem_small(3).0.checked_sub_small(2);
+    match model {
+        FuelModel::Simple => step(mass).unwrap_or_default(),
+        FuelModel::Recursive => {
+            let seven = BigUint::from(7);
+            let mut total = BigUint::zero();
+            let mut fuel = mass.clone();
+            while fuel >= seven {
+                fuel = step(&fuel).unwrap();
+                total += &fuel;
+            }
+            total
+        }
+    }
+}
+
+/// Total fuel with an `i128` sum, for when the `i64` one overflows. Each module takes
+/// the same fuel as in [`checked_total`], negative amounts for small masses included.
+fn wide_total(modules: &[Module], model: FuelModel) -> i128 {
+    modules.iter().map(|m| model.fuel(m.mass) as i128).sum()
+}
+
+fn total(modules: &[Module], model: FuelModel) -> Answer {
+    checked_total(modules, model)
+        .map(Answer::from)
+        .unwrap_or_else(|_| wide_total(modules, model).into())
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<Module>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_modules(input)
+    }
+
+    fn part1(modules: &Self::Input) -> Answer {
+        total(modules, FuelModel::Simple)
+    }
+
+    fn part2(modules: &Self::Input) -> Option<Answer> {
+        Some(total(modules, FuelModel::Recursive))
+    }
+}
+
+#[test]
+fn test_fuel() {
+    let masses = [
+        (12, 2, 2, 1),
+        (14, 2, 2, 1),
+        (1969, 654, 966, 5),
+        (100756, 33583, 50346, 9),
+        (8, 0, 0, 0),
+    ];
+    for &(mass, fuel, total_fuel, steps) in &masses {
+        assert_eq!(FuelModel::Simple.fuel(mass), fuel);
+        assert_eq!(FuelModel::Recursive.fuel(mass), total_fuel);
+        assert_eq!(fuel_steps(mass), steps);
+    }
+}
+
+#[test]
+fn test_max_mass() {
+    for &model in &[FuelModel::Simple, FuelModel::Recursive] {
+        for budget in (-3..100).chain(vec![654, 966, 50346, 1_000_000]) {
+            match model.max_mass(budget) {
+                Some(mass) => {
+                    assert!(model.fuel(mass) <= budget);
+                    assert!(model.fuel(mass + 1) > budget);
+                }
+                None => assert!(model.fuel(0) > budget),
+            }
+        }
+    }
+    assert_eq!(FuelModel::Simple.max_mass(654), Some(1970));
+    assert_eq!(FuelModel::Recursive.max_mass(0), Some(8));
+    assert_eq!(FuelModel::Simple.max_mass(-3), None);
+    // budgets past a third of the range reach masses up to `i64::MAX`
+    for &model in &[FuelModel::Simple, FuelModel::Recursive] {
+        assert_eq!(model.max_mass(i64::MAX), Some(i64::MAX));
+        assert_eq!(model.max_mass(i64::MAX / 2), Some(i64::MAX));
+    }
+    let budget = i64::MAX / 3 - 3;
+    let mass = FuelModel::Simple.max_mass(budget).unwrap();
+    assert!(FuelModel::Simple.fuel(mass) <= budget && FuelModel::Simple.fuel(mass + 1) > budget);
+}
+
+#[test]
+fn test_report() {
+    let input = "main engine 1969\n12\r\n  \"aux, left\"\t14 \n";
+    let modules = Day01::parse(input).unwrap();
+    assert_eq!(modules[0].label.as_deref(), Some("main engine"));
+    assert_eq!(modules[1].label, None);
+    assert_eq!(
+        to_csv(&fuel_report(&modules)),
+        "label,mass,fuel,total_fuel,steps\n\
+         main engine,1969,654,966,5\n\
+         ,12,2,2,1\n\
+         \"\"\"aux, left\"\"\",14,2,2,1\n"
+    );
+    let err = Day01::parse("12\nbig 1e5\n").unwrap_err();
+    assert_eq!((err.line, err.column, err.token.as_str()), (2, 5, "1e5"));
+    let modules = Day01::parse("engine\u{a0}1969\n").unwrap();
+    assert_eq!(modules[0].label.as_deref(), Some("engine"));
+    assert_eq!(modules[0].mass, 1969);
+    let err = Day01::parse("wing\u{a0}12x\n").unwrap_err();
+    assert_eq!((err.column, err.token.as_str()), (6, "12x"));
+}
+
+#[test]
+fn test_overflow() {
+    let module = |mass| Module { label: None, mass };
+    let mut modules = vec![module(i64::MAX); 4];
+    assert_eq!(
+        checked_total(&modules, FuelModel::Simple),
+        Err(OverflowError { module: 3 })
+    );
+    let simple = 4 * (i64::MAX as i128 / 3 - 2);
+    assert_eq!(Day01::part1(&modules), Answer::Text(simple.to_string()));
+    // small modules take the same negative fuel whether or not the total overflows
+    modules.extend(vec![module(1), module(4)]);
+    let simple = simple - 2 - 1;
+    assert_eq!(Day01::part1(&modules), Answer::Text(simple.to_string()));
+    assert_eq!(Day01::part1(&modules[4..].to_vec()), Answer::Int(-3));
+    assert_eq!(
+        Day01::part1(&modules[..1].to_vec()),
+        Answer::Int(i64::MAX / 3 - 2)
+    );
+}
+
+#[test]
+fn test_fuel_paths() {
+    use std::convert::TryFrom;
+
+    let masses = (0..2000)
+        .chain(vec![100756, 1 << 40, i64::MAX as u128, u128::MAX / 7])
+        .chain(vec![u128::MAX - 3, u128::MAX - 2, u128::MAX]);
+    for mass in masses {
+        let recursive = fuel_sequence_u128(mass);
+        assert_eq!(fast_recursive_fuel(mass), recursive, "{}", mass);
+        assert_eq!(fuel_u128(FuelModel::Recursive, mass), recursive);
+        let big = BigUint::from(mass);
+        assert_eq!(
+            fuel_big(FuelModel::Recursive, &big).to_u128(),
+            Some(recursive)
+        );
+        let simple = fuel_u128(FuelModel::Simple, mass);
+        assert_eq!(fuel_big(FuelModel::Simple, &big).to_u128(), Some(simple));
+        if let Ok(mass) = i64::try_from(mass) {
+            assert_eq!(FuelModel::Recursive.fuel(mass) as u128, recursive);
+            assert_eq!(FuelModel::Simple.fuel(mass).max(0) as u128, simple);
+        }
+    }
+    let huge: BigUint = "1000000000000000000000000000000000000000000000"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        fuel_big(FuelModel::Recursive, &huge).to_string(),
+        "499999999999999999999999999999999999999999672"
+    );
+}
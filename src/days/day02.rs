@@ -0,0 +1,59 @@
+use crate::{try_parse_ints, Answer, ParseError, Solution};
+
+fn step(data: &mut [usize], i: usize) -> Option<usize> {
+    let opcode = data[i];
+    if opcode == 99 {
+        Some(data[0])
+    } else {
+        let (i_left, i_right, i_out) = (data[i + 1], data[i + 2], data[i + 3]);
+        let (left, right) = (data[i_left], data[i_right]);
+        data[i_out] = if opcode == 1 {
+            left + right
+        } else {
+            left * right
+        };
+        None
+    }
+}
+
+fn run(data: &[usize], noun: usize, verb: usize) -> usize {
+    let mut data = data.to_vec();
+    data[1] = noun;
+    data[2] = verb;
+    (0..data.len())
+        .step_by(4)
+        .filter_map(|i| step(&mut data, i))
+        .next()
+        .unwrap()
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        try_parse_ints(input, ',')
+    }
+
+    fn part1(data: &Self::Input) -> Answer {
+        run(data, 12, 2).into()
+    }
+
+    fn part2(data: &Self::Input) -> Option<Answer> {
+        (0..=99)
+            .filter_map(|noun| {
+                (0..=99)
+                    .filter_map(|verb| {
+                        if run(data, noun, verb) == 19690720 {
+                            Some(noun * 100 + verb)
+                        } else {
+                            None
+                        }
+                    })
+                    .next()
+            })
+            .next()
+            .map(Answer::from)
+    }
+}
@@ -0,0 +1,518 @@
+use std::cell::OnceCell;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use crate::grid::{Direction, Point};
+use crate::{tokenize, Answer, ParseError, ParseOptions, Solution};
+
+/// A straight run of a wire, along with the number of steps the wire took before it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Segment {
+    start: Point,
+    dir: Direction,
+    len: i64,
+    steps: i64,
+}
+
+impl Segment {
+    fn end(&self) -> Point {
+        self.start + self.dir.delta() * self.len
+    }
+
+    fn is_horizontal(&self) -> bool {
+        self.dir.delta().y == 0
+    }
+
+    /// The range of the coordinate along the segment and the fixed coordinate across it.
+    fn span(&self) -> ((i64, i64), i64) {
+        let (start, end) = (self.start, self.end());
+        if self.is_horizontal() {
+            ((start.x.min(end.x), start.x.max(end.x)), start.y)
+        } else {
+            ((start.y.min(end.y), start.y.max(end.y)), start.x)
+        }
+    }
+
+    fn contains(&self, pos: Point) -> bool {
+        let ((lo, hi), at) = self.span();
+        let (along, across) = if self.is_horizontal() {
+            (pos.x, pos.y)
+        } else {
+            (pos.y, pos.x)
+        };
+        across == at && (lo..=hi).contains(&along)
+    }
+
+    /// Steps taken by the wire when it reaches a point on this segment.
+    fn steps_to(&self, pos: Point) -> i64 {
+        self.steps + self.start.manhattan(pos)
+    }
+}
+
+/// Reads one wire, such as `R8,U5,L5`, into its turns. Directions may be lowercase,
+/// and whitespace around segments or between a direction and its length is ignored.
+/// The wire starts at byte `start` of `input`, which errors are located in. Wires
+/// whose total length doesn't fit in an `i64` are rejected, so that no position or
+/// step count along them overflows.
+pub fn parse_path(input: &str, start: usize) -> Result<Vec<(Direction, u64)>, ParseError> {
+    let line = input[start..].lines().next().unwrap_or("");
+    let mut steps = 0i64;
+    tokenize(line, ',', ParseOptions::default())
+        .enumerate()
+        .map(|(i, (offset, token))| {
+            let error = |reason: String| {
+                let reason = format!("segment {}: {}", i + 1, reason);
+                ParseError::new(input, start + offset, token, reason)
+            };
+            let mut chars = token.chars();
+            let dir = match chars.next() {
+                Some(c) => Direction::from_char(c).filter(|_| c.is_ascii_alphabetic()),
+                None => return Err(error("empty segment".into())),
+            }
+            .ok_or_else(|| error("expected a direction out of U, R, D, L".into()))?;
+            let len: u64 = chars
+                .as_str()
+                .trim_start()
+                .parse()
+                .map_err(|err| error(format!("invalid length: {}", err)))?;
+            if len > i64::MAX as u64 {
+                return Err(error("length is out of range".into()));
+            }
+            steps = steps
+                .checked_add(len as i64)
+                .ok_or_else(|| error("wire leaves the representable range".into()))?;
+            Ok((dir, len))
+        })
+        .collect()
+}
+
+fn to_segments(path: &[(Direction, u64)]) -> Vec<Segment> {
+    let mut pos = Point::ORIGIN;
+    let mut steps = 0;
+    path.iter()
+        .map(|&(dir, len)| {
+            let len = len as i64;
+            let segment = Segment {
+                start: pos,
+                dir,
+                len,
+                steps,
+            };
+            pos = segment.end();
+            steps += len;
+            segment
+        })
+        .collect()
+}
+
+/// Points shared by two segments. Where they overlap along the same line, only the
+/// ends of the overlap and the point closest to the origin are returned, since both
+/// the distance to the origin and the combined step count are minimized at one of
+/// those.
+fn segment_crossings(a: &Segment, b: &Segment) -> Vec<Point> {
+    let ((a_lo, a_hi), a_at) = a.span();
+    let ((b_lo, b_hi), b_at) = b.span();
+    let point = |along, across| {
+        if a.is_horizontal() {
+            Point::new(along, across)
+        } else {
+            Point::new(across, along)
+        }
+    };
+    if a.is_horizontal() != b.is_horizontal() {
+        if (a_lo..=a_hi).contains(&b_at) && (b_lo..=b_hi).contains(&a_at) {
+            vec![point(b_at, a_at)]
+        } else {
+            vec![]
+        }
+    } else if a_at == b_at && a_lo.max(b_lo) <= a_hi.min(b_hi) {
+        let (lo, hi) = (a_lo.max(b_lo), a_hi.min(b_hi));
+        let mut along = vec![lo, hi, 0.max(lo).min(hi)];
+        along.sort_unstable();
+        along.dedup();
+        along.into_iter().map(|x| point(x, a_at)).collect()
+    } else {
+        vec![]
+    }
+}
+
+/// A point other than the origin where two or more wires meet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Crossing {
+    pub pos: Point,
+    /// Fewest steps each wire takes to get here, `None` for wires that don't.
+    pub steps: Vec<Option<i64>>,
+}
+
+impl Crossing {
+    /// Indices of the wires meeting here.
+    pub fn wires(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.steps.len()).filter(move |&i| self.steps[i].is_some())
+    }
+
+    pub fn n_wires(&self) -> usize {
+        self.wires().count()
+    }
+
+    /// Combined signal delay of all the wires meeting here.
+    pub fn delay(&self) -> i64 {
+        self.steps.iter().flatten().sum()
+    }
+
+    /// Combined signal delay of wires `a` and `b` alone, if both meet here.
+    pub fn pair_delay(&self, a: usize, b: usize) -> Option<i64> {
+        let steps = |i| self.steps.get(i).cloned().flatten();
+        Some(steps(a)? + steps(b)?)
+    }
+}
+
+/// Crossings of any two or more wires, ordered by position. Candidates are found for
+/// every pair of wires and then checked against all the others; where wires overlap
+/// along a line, only the points where the distance or the delay can be smallest are
+/// included.
+pub fn find_crossings(wires: &[Vec<Segment>]) -> Vec<Crossing> {
+    let mut candidates = BTreeSet::new();
+    for (i, wire1) in wires.iter().enumerate() {
+        for wire2 in &wires[i + 1..] {
+            for a in wire1 {
+                for b in wire2 {
+                    for pos in segment_crossings(a, b) {
+                        candidates.insert(pos);
+                    }
+                }
+            }
+        }
+    }
+    candidates.remove(&Point::ORIGIN);
+    candidates
+        .into_iter()
+        .map(|pos| {
+            let steps_to = |wire: &Vec<Segment>| {
+                wire.iter()
+                    .filter(|segment| segment.contains(pos))
+                    .map(|segment| segment.steps_to(pos))
+                    .min()
+            };
+            let steps = wires.iter().map(steps_to).collect();
+            Crossing { pos, steps }
+        })
+        .collect()
+}
+
+/// The crossing of at least `min_wires` wires closest to the origin.
+pub fn closest(crossings: &[Crossing], min_wires: usize) -> Option<&Crossing> {
+    crossings
+        .iter()
+        .filter(|c| c.n_wires() >= min_wires)
+        .min_by_key(|c| (c.pos.norm(), c.pos))
+}
+
+/// The crossing of at least `min_wires` wires with the smallest combined delay.
+pub fn min_delay(crossings: &[Crossing], min_wires: usize) -> Option<&Crossing> {
+    crossings
+        .iter()
+        .filter(|c| c.n_wires() >= min_wires)
+        .min_by_key(|c| (c.delay(), c.pos))
+}
+
+/// The crossing of wires `a` and `b` closest to the origin, whichever other wires
+/// meet there too.
+pub fn closest_for_pair(crossings: &[Crossing], a: usize, b: usize) -> Option<&Crossing> {
+    crossings
+        .iter()
+        .filter(|c| c.pair_delay(a, b).is_some())
+        .min_by_key(|c| (c.pos.norm(), c.pos))
+}
+
+/// The crossing of wires `a` and `b` where the delay of those two alone is smallest,
+/// along with that delay.
+pub fn min_delay_for_pair(crossings: &[Crossing], a: usize, b: usize) -> Option<(&Crossing, i64)> {
+    crossings
+        .iter()
+        .filter_map(|c| Some((c, c.pair_delay(a, b)?)))
+        .min_by_key(|&(c, delay)| (delay, c.pos))
+}
+
+const COLOURS: &[&str] = &[
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#9467bd", "#8c564b", "#e377c2", "#17becf", "#bcbd22",
+];
+
+/// Draws the wires as an SVG image, each in its own colour, with the origin in black,
+/// crossings in grey, and the closest crossing by distance and by delay (of all the
+/// wires, as in the answers) circled in red and blue respectively.
+pub fn to_svg(wires: &[Vec<Segment>]) -> String {
+    let points = || {
+        let ends = wires.iter().flatten().map(|segment| segment.end());
+        ends.chain(Some(Point::ORIGIN))
+    };
+    let min = points().fold(Point::ORIGIN, |a, p| Point::new(a.x.min(p.x), a.y.min(p.y)));
+    let max = points().fold(Point::ORIGIN, |a, p| Point::new(a.x.max(p.x), a.y.max(p.y)));
+    // wires may span nearly the whole `i64` range, so the view box is sized in `i128`
+    let extent = |lo: i64, hi: i64| hi as i128 - lo as i128;
+    let (dx, dy) = (extent(min.x, max.x), extent(min.y, max.y));
+    let size = dx.max(dy).max(1);
+    let (margin, radius) = (size / 20 + 1, size as f64 / 200.0 + 0.5);
+    let (x, y) = (min.x as i128 - margin, min.y as i128 - margin);
+    let (w, h) = (dx + 2 * margin, dy + 2 * margin);
+
+    let mut svg = String::new();
+    let mut out = |line: String| svg.push_str(&line);
+    out(format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\" width=\"{}\" height=\"{}\">\n",
+        x,
+        y,
+        w,
+        h,
+        1000 * w / w.max(h),
+        1000 * h / w.max(h),
+    ));
+    out(format!(
+        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"white\"/>\n",
+        x, y, w, h
+    ));
+    for (i, wire) in wires.iter().enumerate() {
+        let mut path = String::from("0,0");
+        for segment in wire {
+            let end = segment.end();
+            write!(path, " {},{}", end.x, end.y).unwrap();
+        }
+        out(format!(
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\" vector-effect=\"non-scaling-stroke\"><title>wire {}</title></polyline>\n",
+            path,
+            COLOURS[i % COLOURS.len()],
+            i + 1
+        ));
+    }
+    let circle = |pos: Point, r: f64, style: &str, title: &str| {
+        format!(
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" {}><title>{}</title></circle>\n",
+            pos.x, pos.y, r, style, title
+        )
+    };
+    let crossings = find_crossings(wires);
+    for c in &crossings {
+        let names: Vec<_> = c.wires().map(|i| (i + 1).to_string()).collect();
+        let title = format!(
+            "({}, {}): wires {}, distance {}, delay {}",
+            c.pos.x,
+            c.pos.y,
+            names.join(", "),
+            c.pos.norm(),
+            c.delay()
+        );
+        out(circle(c.pos, radius, "fill=\"grey\"", &title));
+    }
+    let ring = |colour| {
+        format!(
+            "fill=\"none\" stroke=\"{}\" stroke-width=\"3\" vector-effect=\"non-scaling-stroke\"",
+            colour
+        )
+    };
+    if let Some(c) = closest(&crossings, wires.len()) {
+        let title = format!("closest: distance {}", c.pos.norm());
+        out(circle(c.pos, 3.0 * radius, &ring("red"), &title));
+    }
+    if let Some(c) = min_delay(&crossings, wires.len()) {
+        let title = format!("shortest delay: {}", c.delay());
+        out(circle(c.pos, 4.0 * radius, &ring("blue"), &title));
+    }
+    out(circle(
+        Point::ORIGIN,
+        1.5 * radius,
+        "fill=\"black\"",
+        "origin",
+    ));
+    out("</svg>\n".into());
+    svg
+}
+
+/// Parsed wires, with their crossings found on first use and shared by both parts.
+#[derive(Clone, Debug)]
+pub struct Wires {
+    pub wires: Vec<Vec<Segment>>,
+    crossings: OnceCell<Vec<Crossing>>,
+}
+
+impl Wires {
+    pub fn crossings(&self) -> &[Crossing] {
+        self.crossings.get_or_init(|| find_crossings(&self.wires))
+    }
+}
+
+pub struct Day03;
+
+/// Answers are for crossings where all the wires meet.
+impl Solution for Day03 {
+    type Input = Wires;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut wires = Vec::new();
+        let mut start = 0;
+        // delays add up the steps of several wires, so their lengths must fit together
+        let mut total_len = 0i64;
+        for line in input.split('\n') {
+            if !line.trim().is_empty() {
+                let wire = to_segments(&parse_path(input, start)?);
+                let len = wire.last().map_or(0, |s| s.steps + s.len);
+                total_len = total_len.checked_add(len).ok_or_else(|| {
+                    let reason = "wires together leave the representable range";
+                    ParseError::new(input, start, line.trim_end(), reason)
+                })?;
+                wires.push(wire);
+            }
+            start += line.len() + 1;
+        }
+        Ok(Wires {
+            wires,
+            crossings: OnceCell::new(),
+        })
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        match closest(input.crossings(), input.wires.len()) {
+            Some(c) => c.pos.norm().into(),
+            None => Answer::Text("no crossing of all the wires".into()),
+        }
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        min_delay(input.crossings(), input.wires.len()).map(|c| c.delay().into())
+    }
+}
+
+#[test]
+fn test_examples() {
+    let examples = [
+        ("R8,U5,L5,D3\nU7,R6,D4,L4", 6, 30),
+        (
+            "R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83",
+            159,
+            610,
+        ),
+        (
+            "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51\nU98,R91,D20,R16,D67,R40,U7,R15,U6,R7",
+            135,
+            410,
+        ),
+        // the wires run along each other from (2, 0) to (5, 0)
+        ("R5,U2\nU1,R2,D1,R5", 2, 6),
+    ];
+    for &(input, distance, steps) in &examples {
+        let input = Day03::parse(input).unwrap();
+        assert_eq!(Day03::part1(&input), Answer::Int(distance));
+        assert_eq!(Day03::part2(&input), Some(Answer::Int(steps)));
+    }
+}
+
+#[test]
+fn test_long_segments() {
+    let input = "R8000000,U5000000,L5000000,D3000000\nU7000000,R6000000,D4000000,L4000000";
+    let input = Day03::parse(input).unwrap();
+    assert_eq!(Day03::part1(&input), Answer::Int(6000000));
+    assert_eq!(Day03::part2(&input), Some(Answer::Int(30000000)));
+}
+
+#[test]
+fn test_many_wires() {
+    let input = "R8,U5,L5,D3\nU7,R6,D4,L4\nL1,U3,R7\n";
+    let wires = Day03::parse(input).unwrap();
+    let crossings = wires.crossings();
+    let summary: Vec<_> = crossings
+        .iter()
+        .map(|c| (c.pos, c.wires().collect::<Vec<_>>(), c.delay()))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (Point::new(0, -3), vec![1, 2], 8),
+            (Point::new(2, -3), vec![1, 2], 28),
+            (Point::new(3, -3), vec![0, 1, 2], 48),
+            (Point::new(6, -5), vec![0, 1], 30),
+            (Point::new(6, -3), vec![1, 2], 28),
+        ]
+    );
+    assert_eq!(closest(crossings, 2).unwrap().pos, Point::new(0, -3));
+    assert_eq!(min_delay(crossings, 2).unwrap().pos, Point::new(0, -3));
+    assert_eq!(closest(crossings, 3).unwrap().pos, Point::new(3, -3));
+    assert_eq!(Day03::part1(&wires), Answer::Int(6));
+    assert_eq!(Day03::part2(&wires), Some(Answer::Int(48)));
+
+    let pos = |c: Option<&Crossing>| c.map(|c| c.pos);
+    assert_eq!(
+        pos(closest_for_pair(crossings, 0, 1)),
+        Some(Point::new(3, -3))
+    );
+    assert_eq!(
+        pos(closest_for_pair(crossings, 2, 1)),
+        Some(Point::new(0, -3))
+    );
+    let (c, delay) = min_delay_for_pair(crossings, 0, 1).unwrap();
+    assert_eq!((c.pos, delay), (Point::new(6, -5), 30));
+    let (c, delay) = min_delay_for_pair(crossings, 0, 2).unwrap();
+    assert_eq!((c.pos, delay), (Point::new(3, -3), 28));
+    assert_eq!(closest_for_pair(crossings, 0, 3), None);
+}
+
+#[test]
+fn test_svg() {
+    let wires = Day03::parse("R8,U5,L5,D3\nU7,R6,D4,L4").unwrap();
+    let svg = to_svg(&wires.wires);
+    assert!(svg.starts_with("<svg ") && svg.ends_with("</svg>\n"));
+    assert!(svg.contains("viewBox=\"-1 -8 10 9\""));
+    assert!(svg.contains("points=\"0,0 8,0 8,-5 3,-5 3,-2\""));
+    assert!(svg.contains("points=\"0,0 0,-7 6,-7 6,-3 2,-3\""));
+    assert_eq!(svg.matches("<polyline").count(), 2);
+    // two crossings, the two highlights and the origin
+    assert_eq!(svg.matches("<circle").count(), 5);
+    assert!(svg.contains("<title>closest: distance 6</title>"));
+    assert!(svg.contains("<title>shortest delay: 30</title>"));
+
+    let wires = Day03::parse("R4000000000000000000\nL4000000000000000000,U1").unwrap();
+    let svg = to_svg(&wires.wires);
+    assert!(svg.contains("viewBox=\"-4400000000000000001 -400000000000000002 8800000000000000002 800000000000000003\""));
+    assert!(svg.contains("width=\"1000\" height=\"90\""));
+}
+
+#[test]
+fn test_parse_path() {
+    use Direction::*;
+
+    let parse = |s: &str| parse_path(s, 0);
+    assert_eq!(
+        parse("R8,U5,l5,d3").unwrap(),
+        [(Right, 8), (Up, 5), (Left, 5), (Down, 3)]
+    );
+    assert_eq!(
+        parse(" r 8 ,\tU5 ,L 0\r").unwrap(),
+        [(Right, 8), (Up, 5), (Left, 0)]
+    );
+    let error = |s| parse(s).unwrap_err().to_string();
+    assert_eq!(
+        error("R8,X5"),
+        "invalid token \"X5\" at line 1, column 4 (byte 3): \
+         segment 2: expected a direction out of U, R, D, L"
+    );
+    assert!(error("R8,,U5").ends_with("segment 2: empty segment"));
+    assert!(error("R8,^5").contains("segment 2: expected a direction"));
+    assert!(error("R-1").contains("segment 1: invalid length"));
+    assert!(error("R99999999999999999999").contains("invalid length"));
+    assert!(error("R9999999999999999999").ends_with("length is out of range"));
+    assert!(parse("R9223372036854775807,L9223372036854775807").is_err());
+    let err = Day03::parse("R9223372036854775807,R5\nU1,R3,D5").unwrap_err();
+    assert_eq!((err.line, err.column, err.token.as_str()), (1, 22, "R5"));
+    assert_eq!(err.reason, "segment 2: wire leaves the representable range");
+    let err = Day03::parse("R8\nU5000000000000000000\nL5000000000000000000").unwrap_err();
+    assert_eq!((err.line, err.column), (3, 1));
+    assert_eq!(err.reason, "wires together leave the representable range");
+    let err = Day03::parse("R8,U5\n\nU7,R6,D4,Q4\n").unwrap_err();
+    assert_eq!((err.line, err.column, err.token.as_str()), (3, 10, "Q4"));
+
+    // layouts without a crossing of all the wires still parse, for drawing
+    let wires = Day03::parse("R8,U5\nL3,D2").unwrap();
+    assert_eq!(
+        Day03::part1(&wires),
+        Answer::Text("no crossing of all the wires".into())
+    );
+    assert_eq!(Day03::part2(&wires), None);
+    assert_eq!(Day03::parse("R8,U5\n").unwrap().wires.len(), 1);
+}
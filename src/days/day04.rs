@@ -0,0 +1,383 @@
+use std::collections::BTreeMap;
+use std::ops::{BitAnd, BitOr, Not};
+
+use crate::{try_parse_ints, Answer, ParseError, Solution};
+
+fn to_digits(password: u128) -> Vec<u8> {
+    password.to_string().bytes().map(|b| b - b'0').collect()
+}
+
+/// Lengths of the runs of equal digits, in order.
+fn run_lengths(digits: &[u8]) -> Vec<usize> {
+    let mut runs: Vec<usize> = Vec::new();
+    for (i, &digit) in digits.iter().enumerate() {
+        match runs.last_mut() {
+            Some(run) if digits[i - 1] == digit => *run += 1,
+            _ => runs.push(1),
+        }
+    }
+    runs
+}
+
+/// A condition on a password, combined with others using `&`, `|` and `!`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rule {
+    /// Digits never decrease going from left to right.
+    NonDecreasing,
+    /// Two adjacent digits are the same, possibly as part of a longer run.
+    HasPair,
+    /// Some digit is repeated exactly this many times in a row.
+    HasRunOf(usize),
+    /// Only digits from the set are used, as a bit mask with bit `d` for digit `d`.
+    DigitsIn(u16),
+    /// The password has this many digits.
+    Length(usize),
+    /// The password lies within the inclusive range.
+    InRange(u128, u128),
+    All(Vec<Rule>),
+    Any(Vec<Rule>),
+    Not(Box<Rule>),
+}
+
+impl Rule {
+    pub fn digits_in(digits: &[u8]) -> Self {
+        Rule::DigitsIn(digits.iter().fold(0, |mask, &d| mask | 1 << d))
+    }
+
+    /// Passwords valid for the first part of the puzzle.
+    pub fn part1() -> Self {
+        Rule::NonDecreasing & Rule::HasPair
+    }
+
+    /// Passwords valid for the second part of the puzzle.
+    pub fn part2() -> Self {
+        Rule::NonDecreasing & Rule::HasRunOf(2)
+    }
+
+    pub fn matches(&self, password: u128) -> bool {
+        self.check(password, &to_digits(password))
+    }
+
+    fn check(&self, password: u128, digits: &[u8]) -> bool {
+        match self {
+            Rule::NonDecreasing => digits.windows(2).all(|w| w[0] <= w[1]),
+            Rule::HasPair => digits.windows(2).any(|w| w[0] == w[1]),
+            Rule::HasRunOf(n) => run_lengths(digits).contains(n),
+            Rule::DigitsIn(mask) => digits.iter().all(|&d| mask & 1 << d != 0),
+            Rule::Length(n) => digits.len() == *n,
+            Rule::InRange(lo, hi) => (lo..=hi).contains(&&password),
+            Rule::All(rules) => rules.iter().all(|r| r.check(password, digits)),
+            Rule::Any(rules) => rules.iter().any(|r| r.check(password, digits)),
+            Rule::Not(rule) => !rule.check(password, digits),
+        }
+    }
+
+    /// Number of passwords in `lo..=hi` matching the rule, checked one by one.
+    pub fn count(&self, lo: u128, hi: u128) -> u128 {
+        (lo..=hi).filter(|&p| self.matches(p)).count() as u128
+    }
+}
+
+impl BitAnd for Rule {
+    type Output = Rule;
+
+    fn bitand(self, other: Rule) -> Rule {
+        match self {
+            Rule::All(mut rules) => {
+                rules.push(other);
+                Rule::All(rules)
+            }
+            rule => Rule::All(vec![rule, other]),
+        }
+    }
+}
+
+impl BitOr for Rule {
+    type Output = Rule;
+
+    fn bitor(self, other: Rule) -> Rule {
+        match self {
+            Rule::Any(mut rules) => {
+                rules.push(other);
+                Rule::Any(rules)
+            }
+            rule => Rule::Any(vec![rule, other]),
+        }
+    }
+}
+
+impl Not for Rule {
+    type Output = Rule;
+
+    fn not(self) -> Rule {
+        match self {
+            Rule::Not(rule) => *rule,
+            rule => Rule::Not(Box::new(rule)),
+        }
+    }
+}
+
+/// Which runs of equal digits count as the pair a password needs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PairRule {
+    /// Any run of two or more, as in part 1.
+    AtLeastTwo,
+    /// A run of exactly two, as in part 2.
+    ExactlyTwo,
+}
+
+impl PairRule {
+    /// Longest run length worth telling apart; longer runs are counted as this.
+    fn cap(self) -> u8 {
+        match self {
+            PairRule::AtLeastTwo => 2,
+            PairRule::ExactlyTwo => 3,
+        }
+    }
+
+    /// Whether a finished run of equal digits of this length is a pair.
+    fn is_pair(self, run: u8) -> bool {
+        match self {
+            PairRule::AtLeastTwo => run >= 2,
+            PairRule::ExactlyTwo => run == 2,
+        }
+    }
+
+    /// The same condition, along with non-decreasing digits, as a [`Rule`].
+    pub fn rule(self) -> Rule {
+        match self {
+            PairRule::AtLeastTwo => Rule::part1(),
+            PairRule::ExactlyTwo => Rule::part2(),
+        }
+    }
+}
+
+/// Number of passwords in `0..=max`: non-decreasing digits with a pair as per `rule`.
+fn count_up_to(max: u128, rule: PairRule) -> u128 {
+    let digits: Vec<u8> = max.to_string().bytes().map(|b| b - b'0').collect();
+    // (tight, started, previous digit, length of the current run, found a pair)
+    let mut states = BTreeMap::new();
+    states.insert((true, false, 0, 0, false), 1u128);
+    for &limit in &digits {
+        let mut next = BTreeMap::new();
+        for (&(tight, started, prev, run, found), &count) in &states {
+            let max_digit = if tight { limit } else { 9 };
+            for digit in 0..=max_digit {
+                let tight = tight && digit == limit;
+                let state = if !started && digit == 0 {
+                    (tight, false, 0, 0, false)
+                } else if !started {
+                    (tight, true, digit, 1, false)
+                } else if digit < prev {
+                    continue;
+                } else if digit == prev {
+                    (tight, true, digit, (run + 1).min(rule.cap()), found)
+                } else {
+                    (tight, true, digit, 1, found || rule.is_pair(run))
+                };
+                *next.entry(state).or_insert(0) += count;
+            }
+        }
+        states = next;
+    }
+    states
+        .into_iter()
+        .filter(|&((_, started, _, run, found), _)| started && (found || rule.is_pair(run)))
+        .map(|(_, count)| count)
+        .sum()
+}
+
+/// Counts the passwords in `lo..=hi` digit by digit rather than one by one, so that
+/// ranges of any size up to `u128::MAX` take the same time.
+pub fn count_valid(lo: u128, hi: u128, rule: PairRule) -> u128 {
+    if lo > hi {
+        return 0;
+    }
+    let below = match lo {
+        0 => 0,
+        lo => count_up_to(lo - 1, rule),
+    };
+    count_up_to(hi, rule) - below
+}
+
+/// Valid passwords in ascending order. Only non-decreasing numbers are visited: each
+/// is stepped to the next by bumping its last digit below 9 and copying it into the
+/// digits after it.
+#[derive(Clone, Debug)]
+pub struct Passwords {
+    digits: Vec<u8>,
+    hi: u128,
+    rule: PairRule,
+}
+
+impl Passwords {
+    pub fn new(lo: u128, hi: u128, rule: PairRule) -> Self {
+        let mut digits = to_digits(lo);
+        // the smallest non-decreasing number not below `lo`
+        if let Some(i) = (1..digits.len()).find(|&i| digits[i] < digits[i - 1]) {
+            let fill = digits[i - 1];
+            digits[i..].iter_mut().for_each(|d| *d = fill);
+        }
+        Self { digits, hi, rule }
+    }
+
+    fn value(&self) -> Option<u128> {
+        self.digits
+            .iter()
+            .try_fold(0u128, |acc, &d| acc.checked_mul(10)?.checked_add(d as u128))
+    }
+
+    fn advance(&mut self) {
+        match self.digits.iter().rposition(|&d| d < 9) {
+            Some(i) => {
+                let fill = self.digits[i] + 1;
+                self.digits[i..].iter_mut().for_each(|d| *d = fill);
+            }
+            None => self.digits = vec![1; self.digits.len() + 1],
+        }
+    }
+}
+
+impl Iterator for Passwords {
+    type Item = u128;
+
+    fn next(&mut self) -> Option<u128> {
+        loop {
+            let value = self.value().filter(|&value| value <= self.hi)?;
+            let runs = run_lengths(&self.digits);
+            self.advance();
+            if runs.iter().any(|&run| self.rule.is_pair(run as u8)) {
+                return Some(value);
+            }
+        }
+    }
+}
+
+/// The valid password at index `n`, counting from 0, among those in `lo..=hi`.
+pub fn nth_valid(lo: u128, hi: u128, n: u128, rule: PairRule) -> Option<u128> {
+    if count_valid(lo, hi, rule) <= n {
+        return None;
+    }
+    // the smallest `x` with more than `n` valid passwords in `lo..=x`
+    let (mut left, mut right) = (lo, hi);
+    while left < right {
+        let mid = left + (right - left) / 2;
+        if count_valid(lo, mid, rule) > n {
+            right = mid;
+        } else {
+            left = mid + 1;
+        }
+    }
+    Some(left)
+}
+
+/// Index of a valid password among those in `lo..=hi`; `None` if it's not one of them.
+pub fn rank(lo: u128, hi: u128, password: u128, rule: PairRule) -> Option<u128> {
+    if !(lo..=hi).contains(&password) || !rule.rule().matches(password) {
+        return None;
+    }
+    Some(count_valid(lo, password, rule) - 1)
+}
+
+fn parse_range(input: &str) -> Result<(u128, u128), ParseError> {
+    match try_parse_ints(input, '-')?[..] {
+        [lo, hi] => Ok((lo, hi)),
+        _ => {
+            let offset = input.len() - input.trim_start().len();
+            let reason = "expected a range like 100000-999999";
+            Err(ParseError::new(input, offset, input.trim(), reason))
+        }
+    }
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = (u128, u128);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_range(input)
+    }
+
+    fn part1(&(lo, hi): &Self::Input) -> Answer {
+        count_valid(lo, hi, PairRule::AtLeastTwo).into()
+    }
+
+    fn part2(&(lo, hi): &Self::Input) -> Option<Answer> {
+        Some(count_valid(lo, hi, PairRule::ExactlyTwo).into())
+    }
+}
+
+#[test]
+fn test_count_valid() {
+    for &(lo, hi) in &[
+        (152085, 670283),
+        (0, 1000),
+        (111110, 111122),
+        (5, 5),
+        (99, 100),
+    ] {
+        let part1 = Rule::part1().count(lo, hi);
+        let part2 = Rule::part2().count(lo, hi);
+        assert_eq!(count_valid(lo, hi, PairRule::AtLeastTwo), part1);
+        assert_eq!(count_valid(lo, hi, PairRule::ExactlyTwo), part2);
+    }
+    assert_eq!(count_valid(10, 5, PairRule::AtLeastTwo), 0);
+    for &rule in &[PairRule::AtLeastTwo, PairRule::ExactlyTwo] {
+        let all = count_valid(0, u128::MAX, rule);
+        let mid = 10u128.pow(20) + 12345;
+        assert_eq!(
+            count_valid(0, mid, rule) + count_valid(mid + 1, u128::MAX, rule),
+            all
+        );
+    }
+}
+
+#[test]
+fn test_rules() {
+    let part1 = Rule::part1();
+    let part2 = Rule::part2();
+    for &(password, valid1, valid2) in &[
+        (111111, true, false),
+        (223450, false, false),
+        (123789, false, false),
+        (112233, true, true),
+        (123444, true, false),
+        (111122, true, true),
+    ] {
+        assert_eq!(part1.matches(password), valid1, "{}", password);
+        assert_eq!(part2.matches(password), valid2, "{}", password);
+    }
+    let triple = Rule::NonDecreasing & Rule::HasRunOf(3) & !Rule::HasRunOf(2);
+    assert!(triple.matches(111234) && !triple.matches(111224) && !triple.matches(1111));
+    let odd = Rule::digits_in(&[1, 3, 5, 7, 9]) & Rule::Length(3);
+    assert!(odd.matches(135) && !odd.matches(1357) && !odd.matches(134));
+    let either = Rule::InRange(10, 20) | Rule::InRange(30, 40) | Rule::Length(1);
+    assert_eq!(either.count(0, 50), 10 + 11 + 11);
+    assert_eq!(!!Rule::HasPair, Rule::HasPair);
+}
+
+#[test]
+fn test_passwords() {
+    for &rule in &[PairRule::AtLeastTwo, PairRule::ExactlyTwo] {
+        for &(lo, hi) in &[(152085, 670283), (0, 1200), (99, 99), (8, 11), (111, 110)] {
+            let expected: Vec<_> = (lo..=hi).filter(|&p| rule.rule().matches(p)).collect();
+            assert_eq!(Passwords::new(lo, hi, rule).collect::<Vec<_>>(), expected);
+            for (i, &password) in expected.iter().enumerate().step_by(37) {
+                assert_eq!(nth_valid(lo, hi, i as u128, rule), Some(password));
+                assert_eq!(rank(lo, hi, password, rule), Some(i as u128));
+            }
+            assert_eq!(nth_valid(lo, hi, expected.len() as u128, rule), None);
+        }
+    }
+    let last = Passwords::new(u128::MAX - 10u128.pow(20), u128::MAX, PairRule::AtLeastTwo);
+    assert_eq!(last.count(), 0);
+    assert_eq!(rank(0, 1000, 123, PairRule::AtLeastTwo), None);
+    let (lo, hi, rule) = (10u128.pow(30), 10u128.pow(35), PairRule::ExactlyTwo);
+    let n = count_valid(lo, hi, rule) / 2;
+    let password = nth_valid(lo, hi, n, rule).unwrap();
+    assert_eq!(rank(lo, hi, password, rule), Some(n));
+    let next: Vec<_> = Passwords::new(password, hi, rule).take(3).collect();
+    assert_eq!(next[0], password);
+    assert_eq!(nth_valid(lo, hi, n + 2, rule), Some(next[2]));
+}
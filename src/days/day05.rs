@@ -0,0 +1,20 @@
+use crate::intcode::Machine;
+use crate::{try_parse_ints, Answer, ParseError, Solution};
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        try_parse_ints(input, ',')
+    }
+
+    fn part1(data: &Self::Input) -> Answer {
+        Machine::new(data, &[1]).run().output().unwrap().into()
+    }
+
+    fn part2(data: &Self::Input) -> Option<Answer> {
+        Machine::new(data, &[5]).run().output().map(Answer::from)
+    }
+}
@@ -0,0 +1,249 @@
+use crate::graph::{Graph, Interner, Tree};
+use crate::{tokenize, Answer, ParseError, ParseOptions, Solution};
+
+/// Orbits as a tree rooted at `COM`, each object pointing to the ones orbiting it.
+pub struct Orbits {
+    names: Interner,
+    tree: Tree,
+}
+
+/// One `A)B` line: `B` orbits `A`.
+struct Orbit<'a> {
+    offset: usize,
+    line: &'a str,
+    parent: &'a str,
+    child: &'a str,
+}
+
+fn line_number(input: &str, offset: usize) -> usize {
+    1 + input[..offset].matches('\n').count()
+}
+
+/// Builds the map, or reports every line that keeps it from being a tree rooted at
+/// `COM`: lines without a `)`, objects orbiting two others, cycles and objects that
+/// don't lead back to `COM`. Errors are sorted by position.
+pub fn build_map(input: &str) -> Result<Orbits, Vec<ParseError>> {
+    let mut errors = Vec::new();
+    let mut orbits = Vec::new();
+    for (offset, line) in tokenize(input, '\n', ParseOptions::default()) {
+        if line.is_empty() {
+            continue;
+        }
+        match line.find(')') {
+            Some(idx) => orbits.push(Orbit {
+                offset,
+                line,
+                parent: &line[..idx],
+                child: &line[idx + 1..],
+            }),
+            None => errors.push(ParseError::new(input, offset, line, "expected `A)B`")),
+        }
+    }
+
+    // the first orbit of every object wins, and is the one later errors point at
+    let mut names = Interner::new();
+    let mut parents: Vec<Option<(usize, &Orbit)>> = Vec::new();
+    let mut first_seen = Vec::new();
+    for orbit in &orbits {
+        let (parent, child) = (names.intern(orbit.parent), names.intern(orbit.child));
+        parents.resize(names.len(), None);
+        first_seen.resize(names.len(), orbit);
+        match parents[child] {
+            Some((_, earlier)) => {
+                let offset = orbit.offset + orbit.parent.len() + 1;
+                let reason = format!(
+                    "{} already orbits {} on line {}",
+                    orbit.child,
+                    earlier.parent,
+                    line_number(input, earlier.offset)
+                );
+                errors.push(ParseError::new(input, offset, orbit.child, reason));
+            }
+            None => parents[child] = Some((parent, orbit)),
+        }
+    }
+
+    let mut graph = Graph::new(names.len());
+    for (child, parent) in parents.iter().enumerate() {
+        if let Some((parent, _)) = parent {
+            graph.add_edge(*parent, child);
+        }
+    }
+    let root = names.get("COM");
+    if root.is_none() {
+        let reason = "COM is not in the map";
+        errors.push(ParseError::new(input, input.len(), "", reason));
+    }
+    let tree = root.map(|root| Tree::new(&graph, root));
+    let in_tree = |node| tree.as_ref().is_some_and(|tree| tree.depth(node).is_some());
+
+    // walk up from every object left out of the tree, and from COM if it orbits
+    // something, until reaching one that orbits nothing, which is reported as
+    // disconnected, or going round a cycle
+    let (unvisited, on_path, done) = (0, 1, 2);
+    let mut state = vec![unvisited; names.len()];
+    let starts = (0..names.len())
+        .filter(|&node| !in_tree(node) || (Some(node) == root && parents[node].is_some()));
+    for start in starts {
+        let mut path = Vec::new();
+        let mut node = start;
+        while state[node] == unvisited {
+            state[node] = on_path;
+            path.push(node);
+            match parents[node] {
+                Some((parent, _)) => node = parent,
+                None => {
+                    let orbit = first_seen[node];
+                    let reason = format!("{} does not lead to COM", orbit.parent);
+                    errors.push(ParseError::new(input, orbit.offset, orbit.parent, reason));
+                    break;
+                }
+            }
+        }
+        if state[node] == on_path && parents[node].is_some() {
+            let cycle = &path[path.iter().position(|&n| n == node).unwrap()..];
+            let closing = cycle
+                .iter()
+                .filter_map(|&n| parents[n].map(|(_, orbit)| orbit))
+                .max_by_key(|orbit| orbit.offset)
+                .unwrap();
+            let mut loop_names: Vec<_> = cycle.iter().rev().map(|&n| names.name(n)).collect();
+            let first = loop_names
+                .iter()
+                .position(|&n| n == closing.parent)
+                .unwrap();
+            loop_names.rotate_left(first);
+            loop_names.push(loop_names[0]);
+            let reason = format!("orbits form a cycle: {}", loop_names.join(")"));
+            errors.push(ParseError::new(input, closing.offset, closing.line, reason));
+        }
+        path.iter().for_each(|&n| state[n] = done);
+    }
+
+    match tree {
+        Some(tree) if errors.is_empty() => Ok(Orbits { names, tree }),
+        _ => {
+            errors.sort_by_key(|err| err.offset);
+            Err(errors)
+        }
+    }
+}
+
+fn count_orbits(orbits: &Orbits) -> usize {
+    orbits.tree.depths().sum()
+}
+
+/// Orbital transfers between the objects `from` and `to` are orbiting; `None` if
+/// either isn't in the map or is `COM`, which orbits nothing.
+fn min_distance(orbits: &Orbits, from: &str, to: &str) -> Option<usize> {
+    let (from, to) = (orbits.names.get(from)?, orbits.names.get(to)?);
+    let tree = &orbits.tree;
+    tree.distance(tree.parent(from)?, tree.parent(to)?)
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Orbits;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        build_map(input).map_err(|errors| ParseError::combine(errors).unwrap())
+    }
+
+    fn part1(orbits: &Self::Input) -> Answer {
+        count_orbits(orbits).into()
+    }
+
+    fn part2(orbits: &Self::Input) -> Option<Answer> {
+        min_distance(orbits, "YOU", "SAN").map(Answer::from)
+    }
+}
+
+#[test]
+fn test_count_orbits() {
+    let input = "
+    COM)B
+    B)C
+    C)D
+    D)E
+    E)F
+    B)G
+    G)H
+    D)I
+    E)J
+    J)K
+    K)L
+    ";
+    assert_eq!(count_orbits(&build_map(input).unwrap()), 42);
+}
+
+#[test]
+fn test_min_distance() {
+    let input = "
+    COM)B
+    B)C
+    C)D
+    D)E
+    E)F
+    B)G
+    G)H
+    D)I
+    E)J
+    J)K
+    K)L
+    K)YOU
+    I)SAN
+    ";
+    let orbits = build_map(input).unwrap();
+    assert_eq!(min_distance(&orbits, "YOU", "SAN"), Some(4));
+    assert_eq!(min_distance(&orbits, "YOU", "NOPE"), None);
+    let orbits = build_map("COM)SAN\nSAN)YOU").unwrap();
+    assert_eq!(min_distance(&orbits, "YOU", "SAN"), Some(1));
+    assert_eq!(min_distance(&orbits, "YOU", "COM"), None);
+    let orbits = build_map("COM)SAN\nSAN)X\nX)YOU").unwrap();
+    assert_eq!(min_distance(&orbits, "YOU", "SAN"), Some(2));
+    assert_eq!(min_distance(&orbits, "SAN", "YOU"), Some(2));
+}
+
+#[test]
+fn test_invalid_maps() {
+    let reasons = |input| {
+        build_map(input)
+            .err()
+            .unwrap()
+            .into_iter()
+            .map(|err| (err.line, err.column, err.reason))
+            .collect::<Vec<_>>()
+    };
+    let input = "COM)B\nB)C\nC-D\nCOM)C\nE)F\nF)G\nG)E\nX)Y\nY)Z\n";
+    assert_eq!(
+        reasons(input),
+        [
+            (3, 1, "expected `A)B`".to_string()),
+            (4, 5, "C already orbits B on line 2".into()),
+            (7, 1, "orbits form a cycle: G)E)F)G".into()),
+            (8, 1, "X does not lead to COM".into()),
+        ]
+    );
+    assert_eq!(
+        reasons("A)B\nB)A\nC)D\n"),
+        [
+            (2, 1, "orbits form a cycle: B)A)B".to_string()),
+            (3, 1, "C does not lead to COM".into()),
+            (4, 1, "COM is not in the map".into()),
+        ]
+    );
+    assert_eq!(
+        reasons("COM)A\nA)COM\n"),
+        [(2, 1, "orbits form a cycle: A)COM)A".to_string())]
+    );
+    let err = Day06::parse("COM)B\nB-C\nX)Y\n").err().unwrap();
+    assert_eq!(err.reason, "expected `A)B`");
+    assert_eq!(err.more.len(), 1);
+    assert!(err
+        .to_string()
+        .ends_with("\ninvalid token \"X\" at line 3, column 1 (byte 10): X does not lead to COM"));
+    let err = Day06::parse("COM)B\nB)C\nC)B\n").err().unwrap();
+    assert_eq!((err.line, err.column, err.token.as_str()), (3, 3, "B"));
+    assert_eq!(err.reason, "B already orbits COM on line 1");
+}
@@ -0,0 +1,73 @@
+use itertools::Itertools;
+
+use crate::intcode::Machine;
+use crate::{try_parse_ints, Answer, ParseError, Solution};
+
+struct Amplifiers {
+    data: Vec<i64>,
+}
+
+impl Amplifiers {
+    pub fn new(data: &[i64]) -> Self {
+        Self { data: data.into() }
+    }
+
+    pub fn run(&self, phases: &[i64]) -> i64 {
+        phases.iter().cloned().fold(0, |state, phase| {
+            Machine::new(&self.data, &[phase, state])
+                .run()
+                .output()
+                .unwrap()
+        })
+    }
+
+    pub fn find_best(&self, n: usize) -> (i64, Vec<i64>) {
+        (0..n as i64)
+            .permutations(n)
+            .map(|p: Vec<_>| (self.run(&p), p))
+            .max()
+            .unwrap()
+    }
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        try_parse_ints(input, ',')
+    }
+
+    fn part1(data: &Self::Input) -> Answer {
+        let amp = Amplifiers::new(data);
+        let (answer, _) = amp.find_best(5);
+        answer.into()
+    }
+}
+
+#[test]
+fn test_part1() {
+    let amp = Amplifiers::new(&[
+        3, 15, 3, 16, 1002, 16, 10, 16, 1, 16, 15, 15, 4, 15, 99, 0, 0,
+    ]);
+    let (phases, best) = (vec![4, 3, 2, 1, 0], 43210);
+    assert_eq!(amp.run(&phases), best);
+    assert_eq!(amp.find_best(5), (best, phases));
+
+    let amp = Amplifiers::new(&[
+        3, 23, 3, 24, 1002, 24, 10, 24, 1002, 23, -1, 23, 101, 5, 23, 23, 1, 24, 23, 23, 4, 23, 99,
+        0, 0,
+    ]);
+    let (phases, best) = (vec![0, 1, 2, 3, 4], 54321);
+    assert_eq!(amp.run(&phases), best);
+    assert_eq!(amp.find_best(5), (best, phases));
+
+    let amp = Amplifiers::new(&[
+        3, 31, 3, 32, 1002, 32, 10, 32, 1001, 31, -2, 31, 1007, 31, 0, 33, 1002, 33, 7, 33, 1, 33,
+        31, 31, 1, 32, 31, 31, 4, 31, 99, 0, 0, 0,
+    ]);
+    let (phases, best) = (vec![1, 0, 4, 3, 2], 65210);
+    assert_eq!(amp.run(&phases), best);
+    assert_eq!(amp.find_best(5), (best, phases));
+}
@@ -0,0 +1,110 @@
+use crate::grid::Grid;
+use crate::{Answer, ParseError, Solution};
+
+fn count(slice: &[i64], digit: i64) -> usize {
+    slice.iter().filter(|&x| *x == digit).count()
+}
+
+pub struct Image {
+    layers: Vec<Grid<i64>>,
+}
+
+impl Image {
+    /// Reads layers of `width` by `height` digits, failing on anything that isn't a
+    /// digit or on a partial layer.
+    pub fn from_str(string: &str, width: usize, height: usize) -> Result<Self, ParseError> {
+        let start = string.len() - string.trim_start().len();
+        let body = string.trim();
+        let data = body
+            .char_indices()
+            .map(|(i, c)| match c.to_digit(10) {
+                Some(digit) => Ok(digit as i64),
+                None => {
+                    let token = &body[i..i + c.len_utf8()];
+                    Err(ParseError::new(
+                        string,
+                        start + i,
+                        token,
+                        "expected a digit",
+                    ))
+                }
+            })
+            .collect::<Result<Vec<i64>, _>>()?;
+        let size = width * height;
+        if size == 0 || data.is_empty() || data.len() % size != 0 {
+            let reason = format!(
+                "expected whole layers of {} digits, found {} digits",
+                size,
+                data.len()
+            );
+            return Err(ParseError::new(string, start + body.len(), "", reason));
+        }
+        let layers = data
+            .chunks(size)
+            .map(|layer| Grid::from_vec(width, layer.to_vec()).unwrap())
+            .collect();
+        Ok(Self { layers })
+    }
+
+    pub fn iter_layers(&self) -> impl Iterator<Item = &[i64]> {
+        self.layers.iter().map(Grid::cells)
+    }
+
+    pub fn render(&self) -> Grid<i64> {
+        let first = &self.layers[0];
+        let pixels = first
+            .iter()
+            .map(|(point, _)| {
+                self.layers
+                    .iter()
+                    .map(|layer| layer[point])
+                    .find(|&x| x != 2)
+                    .unwrap_or(0)
+            })
+            .collect();
+        Grid::from_vec(first.width(), pixels).unwrap()
+    }
+}
+
+fn to_rows(grid: &Grid<i64>) -> Vec<String> {
+    grid.rows()
+        .map(|row| {
+            row.iter()
+                .map(|&x| if x == 1 { '█' } else { ' ' })
+                .collect()
+        })
+        .collect()
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Image;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Image::from_str(input, 25, 6)
+    }
+
+    fn part1(image: &Self::Input) -> Answer {
+        let objective = |s| (count(s, 0), count(s, 1) * count(s, 2));
+        image.iter_layers().map(objective).min().unwrap().1.into()
+    }
+
+    fn part2(image: &Self::Input) -> Option<Answer> {
+        Some(Answer::Image(to_rows(&image.render())))
+    }
+}
+
+#[test]
+fn test_parse_errors() {
+    let image = Image::from_str("0222112222120000\n", 2, 2).unwrap();
+    assert_eq!(to_rows(&image.render()), [" █", "█ "]);
+    let err = Image::from_str(" 12x3", 2, 2).err().unwrap();
+    assert_eq!((err.column, err.token.as_str()), (4, "x"));
+    let err = Image::from_str("12345", 2, 2).err().unwrap();
+    assert_eq!(
+        err.reason,
+        "expected whole layers of 4 digits, found 5 digits"
+    );
+    assert!(Image::from_str("\n", 2, 2).is_err());
+}
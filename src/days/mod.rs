@@ -0,0 +1,202 @@
+use std::env;
+use std::fs;
+use std::process;
+use std::time::{Duration, Instant};
+
+use crate::input::{current_profile, get_input, load_input, DEFAULT_PROFILE, EXAMPLE_PROFILE};
+use crate::solution::{Answer, Solution};
+use crate::ParseError;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartRun {
+    pub part: u32,
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+/// Answers for the requested parts of a day along with the time each step took.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Run {
+    pub parse: Duration,
+    pub parts: Vec<PartRun>,
+}
+
+impl Run {
+    pub fn answer(&self, part: u32) -> Option<&Answer> {
+        self.parts
+            .iter()
+            .find(|p| p.part == part)
+            .map(|p| &p.answer)
+    }
+
+    pub fn elapsed(&self, part: u32) -> Option<Duration> {
+        self.parts
+            .iter()
+            .find(|p| p.part == part)
+            .map(|p| p.elapsed)
+    }
+
+    pub fn total(&self) -> Duration {
+        self.parse + self.parts.iter().map(|p| p.elapsed).sum::<Duration>()
+    }
+}
+
+/// Type-erased entry for a single day, so that days can be iterated over.
+pub struct Day {
+    pub day: u32,
+    pub bundled: &'static str,
+    /// Example from the puzzle text, used for the example profile.
+    pub example: Option<&'static str>,
+    pub solve: fn(&str, &[u32]) -> Result<Run, ParseError>,
+}
+
+fn solve<S: Solution>(input: &str, parts: &[u32]) -> Result<Run, ParseError> {
+    let start = Instant::now();
+    let input = S::parse(input)?;
+    let parse = start.elapsed();
+    let parts = parts
+        .iter()
+        .filter_map(|&part| {
+            let start = Instant::now();
+            let answer = match part {
+                1 => Some(S::part1(&input)),
+                2 => S::part2(&input),
+                _ => None,
+            };
+            let elapsed = start.elapsed();
+            answer.map(|answer| PartRun {
+                part,
+                answer,
+                elapsed,
+            })
+        })
+        .collect();
+    Ok(Run { parse, parts })
+}
+
+pub const DAYS: &[Day] = &[
+    Day {
+        day: 1,
+        bundled: include_str!("../bin/inputs/day-01.txt"),
+        example: Some(include_str!("../bin/inputs/example/day-01.txt")),
+        solve: solve::<day01::Day01>,
+    },
+    Day {
+        day: 2,
+        bundled: include_str!("../bin/inputs/day-02.txt"),
+        example: None,
+        solve: solve::<day02::Day02>,
+    },
+    Day {
+        day: 3,
+        bundled: include_str!("../bin/inputs/day-03.txt"),
+        example: Some(include_str!("../bin/inputs/example/day-03.txt")),
+        solve: solve::<day03::Day03>,
+    },
+    Day {
+        day: 4,
+        bundled: include_str!("../bin/inputs/day-04.txt"),
+        example: None,
+        solve: solve::<day04::Day04>,
+    },
+    Day {
+        day: 5,
+        bundled: include_str!("../bin/inputs/day-05.txt"),
+        example: None,
+        solve: solve::<day05::Day05>,
+    },
+    Day {
+        day: 6,
+        bundled: include_str!("../bin/inputs/day-06.txt"),
+        example: Some(include_str!("../bin/inputs/example/day-06.txt")),
+        solve: solve::<day06::Day06>,
+    },
+    Day {
+        day: 7,
+        bundled: include_str!("../bin/inputs/day-07.txt"),
+        example: Some(include_str!("../bin/inputs/example/day-07.txt")),
+        solve: solve::<day07::Day07>,
+    },
+    Day {
+        day: 8,
+        bundled: include_str!("../bin/inputs/day-08.txt"),
+        example: None,
+        solve: solve::<day08::Day08>,
+    },
+];
+
+impl Day {
+    /// The copy of the input bundled for a profile, if any.
+    pub fn bundled_for(&self, profile: &str) -> Option<&'static str> {
+        match profile {
+            DEFAULT_PROFILE => Some(self.bundled),
+            EXAMPLE_PROFILE => self.example,
+            _ => None,
+        }
+    }
+}
+
+pub fn get_day(day: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+/// Entry point for the per-day binaries: prints the answers for both parts.
+pub fn main(day: u32) {
+    let day = get_day(day).expect("unknown day");
+    let profile = current_profile();
+    let input = get_input(day.day, &profile, day.bundled_for(&profile));
+    let run = (day.solve)(&input, &[1, 2]).unwrap_or_else(|err| {
+        eprintln!("cannot parse input: {}", err);
+        process::exit(1);
+    });
+    run.parts.iter().for_each(|p| println!("{}", p.answer));
+}
+
+/// Entry point for binaries that turn a day's parsed input into a file: the input is
+/// read from the first argument, as by `aoc19 -i`, and the output written to the
+/// second or printed.
+pub fn tool<S: Solution>(
+    name: &str,
+    day: u32,
+    extension: &str,
+    render: impl Fn(&S::Input) -> String,
+) {
+    let args: Vec<_> = env::args().skip(1).collect();
+    if args.len() > 2 {
+        eprintln!("usage: {} [input] [output.{}]", name, extension);
+        process::exit(2);
+    }
+    let day = get_day(day).expect("unknown day");
+    let profile = current_profile();
+    let input = load_input(
+        day.day,
+        &profile,
+        args.first().map(String::as_str),
+        day.bundled_for(&profile),
+    )
+    .unwrap_or_else(|err| {
+        eprintln!("cannot read input: {}", err);
+        process::exit(1);
+    });
+    let parsed = S::parse(&input.text).unwrap_or_else(|err| {
+        eprintln!("cannot parse {}: {}", input.source, err);
+        process::exit(1);
+    });
+    let output = render(&parsed);
+    match args.get(1) {
+        Some(path) => fs::write(path, output).unwrap_or_else(|err| {
+            eprintln!("cannot write {}: {}", path, err);
+            process::exit(1);
+        }),
+        None => print!("{}", output),
+    }
+}
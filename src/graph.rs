@@ -0,0 +1,368 @@
+//! Directed graphs over dense node ids, with names interned separately.
+
+use alloc::collections::{BTreeMap, BinaryHeap, VecDeque};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+
+/// Maps names to dense ids in the order they were first seen.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Interner {
+    names: Vec<String>,
+    ids: BTreeMap<String, usize>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intern(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = self.names.len();
+        self.names.push(name.into());
+        self.ids.insert(name.into(), id);
+        id
+    }
+
+    pub fn get(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).cloned()
+    }
+
+    pub fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+/// A directed graph stored as adjacency lists of `(node, weight)` pairs; edges added
+/// without a weight count as 1.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Graph {
+    adjacency: Vec<Vec<(usize, u64)>>,
+}
+
+impl Graph {
+    pub fn new(n_nodes: usize) -> Self {
+        Self {
+            adjacency: vec![Vec::new(); n_nodes],
+        }
+    }
+
+    /// Builds a graph from named edges, interning the names as they come.
+    pub fn from_named_edges<'a>(
+        edges: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> (Self, Interner) {
+        let mut names = Interner::new();
+        let mut graph = Self::default();
+        for (from, to) in edges {
+            let (from, to) = (names.intern(from), names.intern(to));
+            graph.add_edge(from, to);
+        }
+        (graph, names)
+    }
+
+    pub fn len(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adjacency.is_empty()
+    }
+
+    pub fn add_node(&mut self) -> usize {
+        self.adjacency.push(Vec::new());
+        self.adjacency.len() - 1
+    }
+
+    fn reserve_node(&mut self, node: usize) {
+        if node >= self.adjacency.len() {
+            self.adjacency.resize(node + 1, Vec::new());
+        }
+    }
+
+    /// Adds an edge of weight 1, growing the graph if either node is new.
+    pub fn add_edge(&mut self, from: usize, to: usize) {
+        self.add_weighted_edge(from, to, 1);
+    }
+
+    pub fn add_weighted_edge(&mut self, from: usize, to: usize, weight: u64) {
+        self.reserve_node(from.max(to));
+        self.adjacency[from].push((to, weight));
+    }
+
+    pub fn edges(&self, node: usize) -> &[(usize, u64)] {
+        &self.adjacency[node]
+    }
+
+    pub fn neighbors(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.adjacency[node].iter().map(|&(to, _)| to)
+    }
+
+    /// The same graph with every edge pointing the other way.
+    pub fn reversed(&self) -> Self {
+        let mut graph = Self::new(self.len());
+        for (from, edges) in self.adjacency.iter().enumerate() {
+            for &(to, weight) in edges {
+                graph.add_weighted_edge(to, from, weight);
+            }
+        }
+        graph
+    }
+
+    /// For every node, the BFS predecessor and the number of edges from `start`, if
+    /// it's reachable at all.
+    fn bfs_tree(&self, start: usize) -> Vec<Option<(usize, usize)>> {
+        let mut visited = vec![None; self.len()];
+        visited[start] = Some((start, 0));
+        let mut queue = VecDeque::from(vec![(start, 0)]);
+        while let Some((node, distance)) = queue.pop_front() {
+            for next in self.neighbors(node) {
+                if visited[next].is_none() {
+                    visited[next] = Some((node, distance + 1));
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+        visited
+    }
+
+    /// Number of edges on the shortest path from `start` to each node.
+    pub fn bfs(&self, start: usize) -> Vec<Option<usize>> {
+        self.bfs_tree(start)
+            .into_iter()
+            .map(|v| v.map(|(_, distance)| distance))
+            .collect()
+    }
+
+    /// Nodes reachable from `start` in depth-first preorder, following edges in the
+    /// order they were added.
+    pub fn dfs(&self, start: usize) -> Vec<usize> {
+        let mut visited = vec![false; self.len()];
+        let mut order = Vec::new();
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            if visited[node] {
+                continue;
+            }
+            visited[node] = true;
+            order.push(node);
+            stack.extend(
+                self.neighbors(node)
+                    .filter(|&n| !visited[n])
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .rev(),
+            );
+        }
+        order
+    }
+
+    /// A path with the fewest edges from `from` to `to`, both ends included.
+    pub fn shortest_path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        let tree = self.bfs_tree(from);
+        tree[to]?;
+        let mut path = vec![to];
+        let mut node = to;
+        while node != from {
+            node = tree[node]?.0;
+            path.push(node);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Total weight of the lightest path from `start` to each node.
+    pub fn dijkstra(&self, start: usize) -> Vec<Option<u64>> {
+        let mut distances = vec![None; self.len()];
+        let mut heap = BinaryHeap::new();
+        heap.push(Reverse((0, start)));
+        while let Some(Reverse((distance, node))) = heap.pop() {
+            if distances[node].is_some() {
+                continue;
+            }
+            distances[node] = Some(distance);
+            for &(next, weight) in self.edges(node) {
+                if distances[next].is_none() {
+                    heap.push(Reverse((distance + weight, next)));
+                }
+            }
+        }
+        distances
+    }
+
+    /// Orders the nodes so that every edge points forward; `None` if there's a cycle.
+    pub fn topological_sort(&self) -> Option<Vec<usize>> {
+        let mut in_degree = vec![0; self.len()];
+        self.adjacency
+            .iter()
+            .flatten()
+            .for_each(|&(to, _)| in_degree[to] += 1);
+        let mut queue: VecDeque<_> = (0..self.len()).filter(|&n| in_degree[n] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for next in self.neighbors(node) {
+                in_degree[next] -= 1;
+                if in_degree[next] == 0 {
+                    queue.push_back(next);
+                }
+            }
+        }
+        if order.len() == self.len() {
+            Some(order)
+        } else {
+            None
+        }
+    }
+}
+
+/// Parents and depths of the nodes reachable from a root, following the graph's edges
+/// from parents to children.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tree {
+    root: usize,
+    parents: Vec<Option<usize>>,
+    depths: Vec<Option<usize>>,
+}
+
+impl Tree {
+    pub fn new(graph: &Graph, root: usize) -> Self {
+        let bfs = graph.bfs_tree(root);
+        let parents = bfs
+            .iter()
+            .enumerate()
+            .map(|(node, v)| v.map(|(parent, _)| parent).filter(|_| node != root))
+            .collect();
+        let depths = bfs.iter().map(|v| v.map(|(_, depth)| depth)).collect();
+        Self {
+            root,
+            parents,
+            depths,
+        }
+    }
+
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    /// `None` for the root and for nodes that aren't in the tree.
+    pub fn parent(&self, node: usize) -> Option<usize> {
+        self.parents[node]
+    }
+
+    pub fn depth(&self, node: usize) -> Option<usize> {
+        self.depths[node]
+    }
+
+    /// Depths of all the nodes in the tree.
+    pub fn depths(&self) -> impl Iterator<Item = usize> + '_ {
+        self.depths.iter().flatten().cloned()
+    }
+
+    pub fn lowest_common_ancestor(&self, a: usize, b: usize) -> Option<usize> {
+        let (mut a, mut b) = (a, b);
+        let (mut depth_a, mut depth_b) = (self.depth(a)?, self.depth(b)?);
+        while depth_a > depth_b {
+            a = self.parents[a]?;
+            depth_a -= 1;
+        }
+        while depth_b > depth_a {
+            b = self.parents[b]?;
+            depth_b -= 1;
+        }
+        while a != b {
+            a = self.parents[a]?;
+            b = self.parents[b]?;
+        }
+        Some(a)
+    }
+
+    /// Number of edges between two nodes, going through their common ancestor.
+    pub fn distance(&self, a: usize, b: usize) -> Option<usize> {
+        let lca = self.lowest_common_ancestor(a, b)?;
+        let lca = self.depth(lca)?;
+        Some(self.depth(a)? + self.depth(b)? - 2 * lca)
+    }
+}
+
+#[test]
+fn test_interner() {
+    let mut names = Interner::new();
+    assert_eq!(names.intern("b"), 0);
+    assert_eq!(names.intern("a"), 1);
+    assert_eq!(names.intern("b"), 0);
+    assert_eq!((names.get("a"), names.get("c")), (Some(1), None));
+    assert_eq!((names.name(1), names.len()), ("a", 2));
+}
+
+#[test]
+fn test_traversal() {
+    let (graph, names) = Graph::from_named_edges(vec![
+        ("a", "b"),
+        ("a", "c"),
+        ("b", "d"),
+        ("c", "d"),
+        ("d", "e"),
+        ("f", "a"),
+    ]);
+    let id = |name| names.get(name).unwrap();
+    let name = |ids: Vec<usize>| ids.into_iter().map(|id| names.name(id)).collect::<Vec<_>>();
+    assert_eq!(name(graph.dfs(id("a"))), ["a", "b", "d", "e", "c"]);
+    assert_eq!(graph.bfs(id("a"))[id("e")], Some(3));
+    assert_eq!(graph.bfs(id("a"))[id("f")], None);
+    assert_eq!(
+        name(graph.shortest_path(id("a"), id("e")).unwrap()),
+        ["a", "b", "d", "e"]
+    );
+    assert_eq!(graph.shortest_path(id("e"), id("a")), None);
+    assert_eq!(graph.shortest_path(id("a"), id("a")), Some(vec![id("a")]));
+    assert_eq!(
+        name(graph.topological_sort().unwrap()),
+        ["f", "a", "b", "c", "d", "e"]
+    );
+    assert_eq!(
+        name(graph.reversed().dfs(id("e"))),
+        ["e", "d", "b", "a", "f", "c"]
+    );
+    let mut cyclic = graph.clone();
+    cyclic.add_edge(id("e"), id("f"));
+    assert_eq!(cyclic.topological_sort(), None);
+}
+
+#[test]
+fn test_dijkstra() {
+    let mut graph = Graph::new(4);
+    graph.add_weighted_edge(0, 1, 10);
+    graph.add_weighted_edge(0, 2, 3);
+    graph.add_weighted_edge(2, 1, 4);
+    graph.add_weighted_edge(1, 3, 1);
+    assert_eq!(graph.dijkstra(0), [Some(0), Some(7), Some(3), Some(8)]);
+    assert_eq!(graph.dijkstra(3), [None, None, None, Some(0)]);
+}
+
+#[test]
+fn test_tree() {
+    let edges = [(0, 1), (0, 2), (1, 3), (1, 4), (4, 5), (2, 6)];
+    let mut graph = Graph::new(8);
+    edges.iter().for_each(|&(a, b)| graph.add_edge(a, b));
+    let tree = Tree::new(&graph, 0);
+    assert_eq!((tree.parent(0), tree.parent(5)), (None, Some(4)));
+    assert_eq!(tree.depth(5), Some(3));
+    assert_eq!(tree.depths().sum::<usize>(), 11);
+    assert_eq!(tree.lowest_common_ancestor(5, 3), Some(1));
+    assert_eq!(tree.lowest_common_ancestor(5, 6), Some(0));
+    assert_eq!(tree.lowest_common_ancestor(4, 5), Some(4));
+    assert_eq!(tree.distance(5, 6), Some(5));
+    assert_eq!(tree.distance(7, 0), None);
+}
@@ -0,0 +1,456 @@
+//! Points, directions and grids for the puzzles set on a 2D plane.
+//!
+//! Coordinates follow the screen convention: `x` grows to the right and `y` grows
+//! downwards, so that row `y` of a grid is the `y`-th line of its text.
+
+use alloc::collections::btree_map::{self, BTreeMap};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+use core::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+use core::slice;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(self, other: Self) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Manhattan distance to the origin.
+    pub fn norm(self) -> i64 {
+        self.manhattan(Self::ORIGIN)
+    }
+
+    pub fn step(self, dir: Direction) -> Self {
+        self + dir.delta()
+    }
+
+    /// The four orthogonal neighbors, in the order of [`Direction::ALL`].
+    pub fn neighbors(self) -> [Self; 4] {
+        Direction::ALL.map(|dir| self.step(dir))
+    }
+
+    /// The orthogonal and diagonal neighbors, clockwise starting from the top left.
+    pub fn neighbors8(self) -> [Self; 8] {
+        [
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+        ]
+        .map(|(dx, dy)| self + Point::new(dx, dy))
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Neg for Point {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Self;
+
+    fn mul(self, k: i64) -> Self {
+        Self::new(self.x * k, self.y * k)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise, starting from `Up`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// Accepts both `U`/`R`/`D`/`L` (in either case) and arrows `^`/`>`/`v`/`<`.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'U' | 'u' | '^' => Some(Direction::Up),
+            'R' | 'r' | '>' => Some(Direction::Right),
+            'D' | 'd' | 'v' => Some(Direction::Down),
+            'L' | 'l' | '<' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    pub fn delta(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+}
+
+/// A dense rectangular grid with its top left corner at the origin.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// Panics if `width` is 0, since rows need at least one cell.
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        assert!(width > 0, "a grid must be at least one cell wide");
+        let cells = vec![fill; width * height];
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid out of cells in row-major order; `None` if they don't fill whole
+    /// rows of the given width.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Option<Self> {
+        if width == 0 || cells.len() % width != 0 {
+            return None;
+        }
+        let height = cells.len() / width;
+        Some(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// All cells in row-major order.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    fn offset(&self, point: Point) -> Option<usize> {
+        if self.contains(point) {
+            Some(point.y as usize * self.width + point.x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (0..self.width as i64).contains(&point.x) && (0..self.height as i64).contains(&point.y)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.offset(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.offset(point).map(move |i| &mut self.cells[i])
+    }
+
+    pub fn rows(&self) -> slice::Chunks<'_, T> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        let width = self.width;
+        self.cells.iter().enumerate().map(move |(i, cell)| {
+            let point = Point::new((i % width) as _, (i / width) as _);
+            (point, cell)
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        match self.offset(point) {
+            Some(i) => &self.cells[i],
+            None => panic!(
+                "point ({}, {}) is out of bounds of a {}x{} grid",
+                point.x, point.y, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.offset(point) {
+            Some(i) => &mut self.cells[i],
+            None => panic!(
+                "point ({}, {}) is out of bounds of a {}x{} grid",
+                point.x, point.y, width, height
+            ),
+        }
+    }
+}
+
+/// An unbounded grid that only stores the cells that were set.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SparseGrid<T> {
+    cells: BTreeMap<Point, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self {
+            cells: BTreeMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        self.cells.insert(point, value)
+    }
+
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        self.cells.remove(&point)
+    }
+
+    /// Cells ordered by `x`, then `y`.
+    pub fn iter(&self) -> btree_map::Iter<'_, Point, T> {
+        self.cells.iter()
+    }
+
+    /// Top left and bottom right corners of the set cells, inclusive.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        let mut points = self.cells.keys();
+        let first = *points.next()?;
+        Some(points.fold((first, first), |(min, max), p| {
+            let min = Point::new(min.x.min(p.x), min.y.min(p.y));
+            let max = Point::new(max.x.max(p.x), max.y.max(p.y));
+            (min, max)
+        }))
+    }
+
+    /// Copies the set cells into a dense grid spanning their bounds, filling in the
+    /// gaps; the grid's origin corresponds to the returned point.
+    pub fn to_grid(&self, fill: T) -> Option<(Grid<T>, Point)>
+    where
+        T: Clone,
+    {
+        let (min, max) = self.bounds()?;
+        let size = max - min;
+        let mut grid = Grid::new(size.x as usize + 1, size.y as usize + 1, fill);
+        for (&point, value) in &self.cells {
+            grid[point - min] = value.clone();
+        }
+        Some((grid, min))
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        Self {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SparseGrid<T> {
+    type Item = (&'a Point, &'a T);
+    type IntoIter = btree_map::Iter<'a, Point, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.iter()
+    }
+}
+
+#[test]
+fn test_point() {
+    let (a, b) = (Point::new(1, -2), Point::from((-3, 4)));
+    assert_eq!(a + b, Point::new(-2, 2));
+    assert_eq!(a - b, Point::new(4, -6));
+    assert_eq!(-a * 3, Point::new(-3, 6));
+    assert_eq!(a.manhattan(b), 10);
+    assert_eq!(b.norm(), 7);
+    let mut c = a;
+    c += b;
+    c -= a;
+    assert_eq!(c, b);
+    assert_eq!(
+        Point::ORIGIN.neighbors(),
+        [(0, -1), (1, 0), (0, 1), (-1, 0)].map(Point::from)
+    );
+    assert!(Point::ORIGIN
+        .neighbors8()
+        .iter()
+        .all(|p| p.x.abs() <= 1 && p.y.abs() <= 1));
+}
+
+#[test]
+fn test_direction() {
+    let dir = Direction::from_char('U').unwrap();
+    assert_eq!(Direction::from_char('^'), Some(dir));
+    assert_eq!(Direction::from_char('x'), None);
+    assert_eq!(dir.turn_right(), Direction::Right);
+    assert_eq!(dir.turn_left(), Direction::Left);
+    assert_eq!(dir.reverse(), Direction::Down);
+    for &dir in &Direction::ALL {
+        assert_eq!(dir.turn_left().turn_right(), dir);
+        assert_eq!(dir.delta() + dir.reverse().delta(), Point::ORIGIN);
+    }
+}
+
+#[test]
+fn test_grid() {
+    let mut grid = Grid::from_vec(3, (0..6).collect()).unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[Point::new(2, 1)], 5);
+    assert_eq!(grid.get(Point::new(3, 0)), None);
+    assert_eq!(grid.get(Point::new(0, -1)), None);
+    grid[Point::new(1, 0)] = 10;
+    *grid.get_mut(Point::new(0, 1)).unwrap() = 20;
+    let rows: Vec<_> = grid.rows().collect();
+    assert_eq!(rows, [&[0, 10, 2][..], &[20, 4, 5][..]]);
+    assert_eq!(grid.iter().nth(4), Some((Point::new(1, 1), &4)));
+    assert_eq!(grid.map(|&x| x % 2).cells(), &[0, 0, 0, 0, 0, 1]);
+    assert!(Grid::from_vec(4, vec![0; 6]).is_none());
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn test_grid_out_of_bounds() {
+    let grid = Grid::new(2, 2, 0);
+    let _ = grid[Point::new(2, 0)];
+}
+
+#[test]
+#[should_panic(expected = "at least one cell wide")]
+fn test_grid_zero_width() {
+    Grid::new(0, 3, 0);
+}
+
+#[test]
+fn test_sparse_grid() {
+    use alloc::string::String;
+
+    let mut grid: SparseGrid<char> = [((1, -1), 'a'), ((-1, 2), 'b')]
+        .iter()
+        .map(|&(p, c)| (Point::from(p), c))
+        .collect();
+    assert_eq!(grid.insert(Point::new(0, 0), 'c'), None);
+    assert_eq!(grid.len(), 3);
+    assert_eq!(grid.bounds(), Some((Point::new(-1, -1), Point::new(1, 2))));
+    let (dense, origin) = grid.to_grid('.').unwrap();
+    assert_eq!(origin, Point::new(-1, -1));
+    let text: Vec<String> = dense.rows().map(|row| row.iter().collect()).collect();
+    assert_eq!(text, ["..a", ".c.", "...", "b.."]);
+    assert_eq!(grid.remove(Point::new(0, 0)), Some('c'));
+    assert!(!grid.contains(Point::new(0, 0)));
+    assert!(SparseGrid::<()>::new().bounds().is_none());
+}
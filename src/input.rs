@@ -0,0 +1,136 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// Where a puzzle input was loaded from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    Path(PathBuf),
+    Stdin,
+    Bundled,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::Path(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "<stdin>"),
+            Source::Bundled => write!(f, "<bundled>"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Input {
+    pub text: String,
+    pub source: Source,
+}
+
+pub fn input_dir() -> PathBuf {
+    env::var_os("AOC19_INPUTS").map_or_else(|| "inputs".into(), PathBuf::from)
+}
+
+pub fn input_filename(day: u32) -> String {
+    format!("day-{:02}.txt", day)
+}
+
+/// Profile whose inputs sit directly in the input directory.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Profile holding the small examples from the puzzle texts.
+pub const EXAMPLE_PROFILE: &str = "example";
+
+/// The profile selected by `$AOC19_PROFILE`, or the default one.
+pub fn current_profile() -> String {
+    env::var("AOC19_PROFILE").unwrap_or_else(|_| DEFAULT_PROFILE.into())
+}
+
+/// Path of a day's input relative to the input directory: `day-NN.txt` for the
+/// default profile and `<profile>/day-NN.txt` for any other.
+pub fn profile_path(profile: &str, day: u32) -> PathBuf {
+    let filename = input_filename(day);
+    match profile {
+        DEFAULT_PROFILE => filename.into(),
+        profile => Path::new(profile).join(filename),
+    }
+}
+
+/// Names of the profiles in the input directory, sorted, starting with the default
+/// one; every subdirectory is a profile.
+pub fn profiles() -> io::Result<Vec<String>> {
+    let mut profiles = Vec::new();
+    let dir = input_dir();
+    if dir.is_dir() {
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                profiles.push(entry.file_name().to_string_lossy().into_owned());
+            }
+        }
+    }
+    profiles.retain(|profile| profile != DEFAULT_PROFILE);
+    profiles.sort();
+    profiles.insert(0, DEFAULT_PROFILE.into());
+    Ok(profiles)
+}
+
+fn read_path(path: &Path) -> io::Result<Input> {
+    let text = fs::read_to_string(path)
+        .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))?;
+    let source = Source::Path(path.into());
+    Ok(Input { text, source })
+}
+
+fn read_stdin() -> io::Result<Input> {
+    let mut text = String::new();
+    io::stdin().read_to_string(&mut text)?;
+    let source = Source::Stdin;
+    Ok(Input { text, source })
+}
+
+/// Loads the input for a day from `arg` if given (`-` meaning stdin), then from the
+/// profile's file in the input directory (`$AOC19_INPUTS`, or `inputs` by default),
+/// then falls back to the `bundled` copy if there is one for this profile.
+pub fn load_input(
+    day: u32,
+    profile: &str,
+    arg: Option<&str>,
+    bundled: Option<&str>,
+) -> io::Result<Input> {
+    match arg {
+        Some("-") => read_stdin(),
+        Some(path) => read_path(Path::new(path)),
+        None => {
+            let path = input_dir().join(profile_path(profile, day));
+            match bundled {
+                _ if path.is_file() => read_path(&path),
+                Some(text) => {
+                    let text = text.into();
+                    let source = Source::Bundled;
+                    Ok(Input { text, source })
+                }
+                None => Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("no input for profile {}: {}", profile, path.display()),
+                )),
+            }
+        }
+    }
+}
+
+/// Loads the input for a day based on the first command-line argument and the current
+/// profile, exiting with a message if it can't be read; meant to be called from a
+/// day's `main`.
+pub fn get_input(day: u32, profile: &str, bundled: Option<&str>) -> String {
+    let arg = env::args().nth(1);
+    match load_input(day, profile, arg.as_deref(), bundled) {
+        Ok(input) => input.text,
+        Err(err) => {
+            eprintln!("cannot read input: {}", err);
+            process::exit(1);
+        }
+    }
+}
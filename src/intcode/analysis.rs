@@ -0,0 +1,209 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::core::{Command, Op, Param};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Instruction {
+    pub pos: usize,
+    pub command: Command,
+    pub args: Vec<i64>,
+}
+
+impl Instruction {
+    pub fn decode(data: &[i64], pos: usize) -> Option<Self> {
+        let command = Command::decode(*data.get(pos)?)?;
+        let args = data.get(pos + 1..pos + 1 + command.op.n_params())?.to_vec();
+        Some(Self { pos, command, args })
+    }
+
+    pub fn op(&self) -> Op {
+        self.command.op
+    }
+
+    pub fn next(&self) -> usize {
+        self.pos + 1 + self.op().n_params()
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = usize> {
+        self.pos..self.next()
+    }
+
+    pub fn out_address(&self) -> Option<usize> {
+        if self.op().has_out() {
+            Some(self.args[self.op().n_in()] as _)
+        } else {
+            None
+        }
+    }
+
+    pub fn jump_target(&self) -> Option<usize> {
+        match (self.op().is_jump(), self.command.params.get(1)) {
+            (true, Some(Param::Immediate)) => Some(self.args[1] as _),
+            _ => None,
+        }
+    }
+
+    pub fn is_dynamic_jump(&self) -> bool {
+        self.op().is_jump() && self.jump_target().is_none()
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Exit {
+    Next(usize),
+    Branch { target: Option<usize>, next: usize },
+    Halt,
+    Interpret(usize),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Block {
+    pub start: usize,
+    pub instructions: Vec<Instruction>,
+    pub exit: Exit,
+}
+
+/// Static control flow of a program, assuming its code is never overwritten.
+///
+/// Every instruction reachable from position 0 through fall-through or immediate jumps
+/// is decoded once; jumps through memory are left for the caller to resolve at runtime.
+/// Writes into decoded code end their block with `Exit::Interpret`, since nothing past
+/// that point can be trusted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Analysis {
+    pub blocks: BTreeMap<usize, Block>,
+    pub code: BTreeSet<usize>,
+}
+
+impl Analysis {
+    pub fn new(data: &[i64]) -> Self {
+        let mut decoded = BTreeMap::<usize, Option<Instruction>>::new();
+        let mut queue = vec![0];
+        while let Some(pos) = queue.pop() {
+            if decoded.contains_key(&pos) {
+                continue;
+            }
+            let instruction = Instruction::decode(data, pos);
+            if let Some(ref instruction) = instruction {
+                if instruction.op() != Op::Exit {
+                    queue.push(instruction.next());
+                }
+                queue.extend(instruction.jump_target());
+            }
+            decoded.insert(pos, instruction);
+        }
+
+        let code: BTreeSet<_> = decoded
+            .values()
+            .flatten()
+            .flat_map(Instruction::cells)
+            .collect();
+        let writes_code = |i: &Instruction| matches!(i.out_address(), Some(a) if code.contains(&a));
+
+        let mut leaders = BTreeSet::new();
+        leaders.insert(0);
+        decoded
+            .iter()
+            .for_each(|(&pos, instruction)| match instruction {
+                Some(i) if i.op().is_jump() || writes_code(i) => {
+                    leaders.extend(i.jump_target());
+                    leaders.insert(i.next());
+                }
+                None => {
+                    leaders.insert(pos);
+                }
+                _ => (),
+            });
+        leaders.retain(|pos| decoded.contains_key(pos));
+
+        let blocks = leaders
+            .iter()
+            .map(|&start| {
+                let mut instructions = Vec::new();
+                let mut pos = start;
+                let exit = loop {
+                    let instruction = match &decoded[&pos] {
+                        Some(instruction) => instruction.clone(),
+                        None => break Exit::Interpret(pos),
+                    };
+                    let (op, next) = (instruction.op(), instruction.next());
+                    let exit = if op == Op::Exit {
+                        Some(Exit::Halt)
+                    } else if writes_code(&instruction) {
+                        Some(Exit::Interpret(next))
+                    } else if op.is_jump() {
+                        let target = instruction.jump_target();
+                        Some(Exit::Branch { target, next })
+                    } else if leaders.contains(&next) {
+                        Some(Exit::Next(next))
+                    } else {
+                        None
+                    };
+                    instructions.push(instruction);
+                    match exit {
+                        Some(exit) => break exit,
+                        None => pos = next,
+                    }
+                };
+                let block = Block {
+                    start,
+                    instructions,
+                    exit,
+                };
+                (start, block)
+            })
+            .collect();
+
+        Self { blocks, code }
+    }
+
+    pub fn instructions(&self) -> impl Iterator<Item = &Instruction> {
+        self.blocks.values().flat_map(|b| b.instructions.iter())
+    }
+
+    pub fn code_ranges(&self) -> Vec<(usize, usize)> {
+        self.code.iter().fold(Vec::new(), |mut ranges, &cell| {
+            match ranges.last_mut() {
+                Some((_, end)) if *end == cell => *end += 1,
+                _ => ranges.push((cell, cell + 1)),
+            }
+            ranges
+        })
+    }
+
+    pub fn has_dynamic_jumps(&self) -> bool {
+        self.instructions().any(Instruction::is_dynamic_jump)
+    }
+
+    pub fn writes_code(&self, instruction: &Instruction) -> bool {
+        matches!(instruction.out_address(), Some(a) if self.code.contains(&a))
+    }
+
+    pub fn is_self_modifying(&self) -> bool {
+        self.instructions().any(|i| self.writes_code(i))
+    }
+}
+
+#[test]
+fn test_analysis() {
+    let data = [
+        3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8, // input == 8
+    ];
+    let analysis = Analysis::new(&data);
+    assert_eq!(analysis.code_ranges(), vec![(0, 9)]);
+    assert_eq!(analysis.blocks.len(), 1);
+    assert_eq!(analysis.blocks[&0].exit, Exit::Halt);
+    assert!(!analysis.is_self_modifying());
+
+    let data = [1, 0, 0, 0, 99];
+    let analysis = Analysis::new(&data);
+    assert_eq!(analysis.blocks[&0].exit, Exit::Interpret(4));
+    assert!(analysis.is_self_modifying());
+
+    let data = [3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1];
+    let analysis = Analysis::new(&data);
+    assert!(analysis.is_self_modifying());
+    assert!(!analysis.has_dynamic_jumps());
+}
@@ -0,0 +1,279 @@
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Param {
+    Position,
+    Immediate,
+}
+
+impl Param {
+    pub fn from_mode(mode: i64) -> Option<Self> {
+        match mode {
+            0 => Some(Param::Position),
+            1 => Some(Param::Immediate),
+            _ => None,
+        }
+    }
+}
+
+impl From<i64> for Param {
+    fn from(value: i64) -> Self {
+        Self::from_mode(value).unwrap_or_else(|| panic!("invalid parameter mode: {}", value))
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Write(i64),
+    Input(i64),
+    Output(i64),
+    Jump(usize),
+    None,
+}
+
+impl Outcome {
+    pub fn jump_if(condition: bool, value: usize) -> Self {
+        if condition {
+            Self::Jump(value)
+        } else {
+            Self::None
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Multiply,
+    Input,
+    Output,
+    JumpIfTrue,
+    JumpIfFalse,
+    LessThan,
+    Equals,
+    Exit,
+}
+
+impl Op {
+    pub fn from_opcode(opcode: i64) -> Option<Self> {
+        Some(match opcode {
+            1 => Op::Add,
+            2 => Op::Multiply,
+            3 => Op::Input,
+            4 => Op::Output,
+            5 => Op::JumpIfTrue,
+            6 => Op::JumpIfFalse,
+            7 => Op::LessThan,
+            8 => Op::Equals,
+            99 => Op::Exit,
+            _ => return None,
+        })
+    }
+
+    pub fn n_in(&self) -> usize {
+        match self {
+            Op::Input | Op::Exit => 0,
+            Op::Output => 1,
+            _ => 2,
+        }
+    }
+
+    pub fn has_out(&self) -> bool {
+        matches!(
+            self,
+            Op::Add | Op::Multiply | Op::Input | Op::LessThan | Op::Equals
+        )
+    }
+
+    pub fn is_jump(&self) -> bool {
+        matches!(self, Op::JumpIfTrue | Op::JumpIfFalse)
+    }
+
+    pub fn n_params(&self) -> usize {
+        self.n_in() + (self.has_out() as usize)
+    }
+
+    pub fn apply(&self, args: &[i64], input: Option<i64>) -> Outcome {
+        match self {
+            Op::Add => Outcome::Write(args[0] + args[1]),
+            Op::Multiply => Outcome::Write(args[0] * args[1]),
+            Op::Input => Outcome::Input(input.expect("no input available")),
+            Op::Output => Outcome::Output(args[0]),
+            Op::JumpIfTrue => Outcome::jump_if(args[0] != 0, args[1] as _),
+            Op::JumpIfFalse => Outcome::jump_if(args[0] == 0, args[1] as _),
+            Op::LessThan => Outcome::Write((args[0] < args[1]) as _),
+            Op::Equals => Outcome::Write((args[0] == args[1]) as _),
+            Op::Exit => Outcome::None,
+        }
+    }
+}
+
+impl From<i64> for Op {
+    fn from(opcode: i64) -> Self {
+        Self::from_opcode(opcode).unwrap_or_else(|| panic!("invalid opcode: {}", opcode))
+    }
+}
+
+/// Decoded instruction header; modes of unused parameter slots are `Param::Position`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Command {
+    pub op: Op,
+    pub params: [Param; 2],
+}
+
+impl Command {
+    pub fn decode(value: i64) -> Option<Self> {
+        let op = Op::from_opcode(value % 100)?;
+        let mut params = [Param::Position; 2];
+        for (i, param) in params.iter_mut().enumerate().take(op.n_in()) {
+            *param = Param::from_mode(value / 10i64.pow(i as u32 + 2) % 10)?;
+        }
+        Some(Self { op, params })
+    }
+
+    pub fn parse_args(&self, data: &[i64], pos: usize) -> [i64; 2] {
+        let mut args = [0; 2];
+        for (i, arg) in args.iter_mut().enumerate().take(self.op.n_in()) {
+            let input = data[pos + i + 1];
+            *arg = match self.params[i] {
+                Param::Position => data[input as usize],
+                Param::Immediate => input,
+            };
+        }
+        args
+    }
+}
+
+impl From<i64> for Command {
+    fn from(value: i64) -> Self {
+        Self::decode(value).unwrap_or_else(|| panic!("invalid instruction: {}", value))
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Step {
+    Running,
+    Output(i64),
+    NeedsInput,
+    Halted,
+}
+
+/// Allocation-free execution core that runs directly on caller-owned memory.
+///
+/// `M` can be a `Vec<i64>`, a fixed-size array or a borrowed `&mut [i64]`; inputs are
+/// supplied one step at a time and outputs are handed back as `Step::Output`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Core<M> {
+    data: M,
+    pos: usize,
+}
+
+impl<M: AsRef<[i64]> + AsMut<[i64]>> Core<M> {
+    pub fn new(data: M) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    pub fn with_pos(mut self, pos: usize) -> Self {
+        self.pos = pos;
+        self
+    }
+
+    pub fn data(&self) -> &[i64] {
+        self.data.as_ref()
+    }
+
+    pub fn into_data(self) -> M {
+        self.data
+    }
+
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    pub fn command(&self) -> Command {
+        Command::from(self.data()[self.pos])
+    }
+
+    fn apply_outcome(&mut self, op: Op, outcome: Outcome) {
+        if let Outcome::Write(value) | Outcome::Input(value) = outcome {
+            let data = self.data.as_mut();
+            let address = data[self.pos + op.n_in() + 1] as usize;
+            data[address] = value;
+        }
+        self.pos = match outcome {
+            Outcome::Jump(pos) => pos,
+            _ => self.pos + 1 + op.n_params(),
+        }
+    }
+
+    /// Whether the instruction at the current position is valid: a known opcode and
+    /// modes, every address it reads or writes in bounds, and no arithmetic overflow.
+    /// `step` panics on anything else.
+    pub fn can_step(&self) -> bool {
+        let data = self.data();
+        let in_bounds = |address: i64| (0..data.len() as i64).contains(&address);
+        let command = match data.get(self.pos).cloned().and_then(Command::decode) {
+            Some(command) => command,
+            None => return false,
+        };
+        let op = command.op;
+        if self.pos + op.n_params() >= data.len() {
+            return false;
+        }
+        let params = &data[self.pos + 1..=self.pos + op.n_params()];
+        let reads_ok =
+            (0..op.n_in()).all(|i| command.params[i] == Param::Immediate || in_bounds(params[i]));
+        if !reads_ok || (op.has_out() && !in_bounds(params[op.n_in()])) {
+            return false;
+        }
+        let args = command.parse_args(data, self.pos);
+        match op {
+            Op::Add => args[0].checked_add(args[1]).is_some(),
+            Op::Multiply => args[0].checked_mul(args[1]).is_some(),
+            _ => true,
+        }
+    }
+
+    /// Executes one instruction, leaving the machine untouched if it needs an input
+    /// that wasn't given.
+    pub fn step(&mut self, input: Option<i64>) -> Step {
+        let command = self.command();
+        let op = command.op;
+        if op == Op::Input && input.is_none() {
+            return Step::NeedsInput;
+        }
+        let args = command.parse_args(self.data(), self.pos);
+        let outcome = op.apply(&args, input);
+        self.apply_outcome(op, outcome);
+        match outcome {
+            _ if op == Op::Exit => Step::Halted,
+            Outcome::Output(value) => Step::Output(value),
+            _ => Step::Running,
+        }
+    }
+}
+
+#[test]
+fn test_core_fixed_buffer() {
+    let mut buffer = [3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8];
+    let mut core = Core::new(&mut buffer[..]);
+    assert_eq!(core.step(None), Step::NeedsInput);
+    assert_eq!(core.pos(), 0);
+    assert_eq!(core.step(Some(8)), Step::Running);
+    assert_eq!(core.step(None), Step::Running);
+    assert_eq!(core.step(None), Step::Output(1));
+    assert_eq!(core.step(None), Step::Halted);
+    assert_eq!(buffer[9], 1);
+}
+
+#[test]
+fn test_core_can_step() {
+    let valid = |data: &[i64]| Core::new(data.to_vec()).can_step();
+    assert!(valid(&[1, 0, 0, 0, 99]) && valid(&[1101, -5, 3, 0, 99]));
+    // write out of bounds, read out of bounds, truncated, bad opcode and mode
+    assert!(!valid(&[1, 0, 0, 100, 99]));
+    assert!(!valid(&[1, -1, 0, 0, 99]));
+    assert!(!valid(&[1, 0, 0]));
+    assert!(!valid(&[42, 0, 0, 0]) && !valid(&[201, 0, 0, 0]));
+    assert!(!valid(&[1102, i64::MAX, 2, 0]));
+    assert!(!valid(&[]) && !Core::new([99]).with_pos(1).can_step());
+    assert!(valid(&[99]) && valid(&[3, 0]) && !valid(&[3, 2]));
+}
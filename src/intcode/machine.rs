@@ -0,0 +1,94 @@
+use alloc::vec::Vec;
+use core::iter;
+
+use super::core::{Command, Core, Op, Step};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Machine {
+    core: Core<Vec<i64>>,
+    inputs: Vec<i64>,
+    outputs: Vec<i64>,
+}
+
+impl Machine {
+    pub fn new(data: &[i64], inputs: &[i64]) -> Self {
+        Self {
+            core: Core::new(data.into()),
+            inputs: inputs.into(),
+            outputs: Vec::new(),
+        }
+    }
+
+    pub fn with_pos(mut self, pos: usize) -> Self {
+        self.core = self.core.with_pos(pos);
+        self
+    }
+
+    pub fn step(&mut self) -> bool {
+        let is_input = self.core.command().op == Op::Input;
+        match self.core.step(self.inputs.first().cloned()) {
+            Step::NeedsInput => panic!("no input available"),
+            Step::Output(value) => self.outputs.push(value),
+            Step::Halted => return false,
+            Step::Running => (),
+        }
+        if is_input {
+            self.inputs.remove(0);
+        }
+        true
+    }
+
+    /// See [`Core::can_step`].
+    pub fn can_step(&self) -> bool {
+        self.core.can_step()
+    }
+
+    pub fn run(&mut self) -> &mut Self {
+        iter::repeat(()).find(|_| !self.step());
+        self
+    }
+
+    pub fn push_input(&mut self, value: i64) {
+        self.inputs.push(value);
+    }
+
+    pub fn needs_input(&self) -> bool {
+        let op = Command::decode(self.data()[self.pos()]).map(|command| command.op);
+        self.inputs.is_empty() && op == Some(Op::Input)
+    }
+
+    pub fn data(&self) -> &[i64] {
+        self.core.data()
+    }
+
+    pub fn pos(&self) -> usize {
+        self.core.pos()
+    }
+
+    pub fn outputs(&self) -> &[i64] {
+        &self.outputs
+    }
+
+    pub fn output(&self) -> Option<i64> {
+        self.outputs.last().cloned()
+    }
+}
+#[test]
+fn test_machine() {
+    let data = [
+        3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0, 0,
+        1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4, 20,
+        1105, 1, 46, 98, 99,
+    ];
+    for input in 0..20 {
+        let output = Machine::new(&data, &[input]).run().output().unwrap();
+        let expected = if input < 8 {
+            999
+        } else if input == 8 {
+            1000
+        } else {
+            1001
+        };
+        assert_eq!(output, expected);
+    }
+}
@@ -0,0 +1,11 @@
+mod analysis;
+mod core;
+mod machine;
+mod optimize;
+mod transpile;
+
+pub use self::analysis::{Analysis, Block, Exit, Instruction};
+pub use self::core::{Command, Core, Op, Outcome, Param, Step};
+pub use self::machine::Machine;
+pub use self::optimize::{Arg, Cmp, Inst, Optimized, Target};
+pub use self::transpile::transpile;
@@ -0,0 +1,225 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use super::analysis::{Analysis, Block, Exit, Instruction};
+use super::core::{Op, Param};
+
+const PRELUDE: &str = r#"
+enum Flow {
+    Block(fn(&mut State) -> Flow),
+    Interpret(usize),
+    Exit,
+}
+
+struct State {
+    mem: Vec<i64>,
+    inputs: Vec<i64>,
+    next_input: usize,
+    outputs: Vec<i64>,
+}
+
+impl State {
+    fn input(&mut self) -> i64 {
+        let value = *self.inputs.get(self.next_input).expect("no input available");
+        self.next_input += 1;
+        value
+    }
+}
+
+/// Final memory of a run along with everything the program printed.
+pub struct Run {
+    pub mem: Vec<i64>,
+    pub outputs: Vec<i64>,
+}
+
+pub fn run(inputs: &[i64]) -> Vec<i64> {
+    execute(&PROGRAM, inputs).outputs
+}
+
+pub fn run_with_memory(data: &[i64], inputs: &[i64]) -> Vec<i64> {
+    execute(data, inputs).outputs
+}
+
+/// Runs a copy of `data`, keeping the final memory for programs that leave their
+/// answer there.
+pub fn execute(data: &[i64], inputs: &[i64]) -> Run {
+    let mut s = State {
+        mem: data.to_vec(),
+        inputs: inputs.to_vec(),
+        next_input: 0,
+        outputs: Vec::new(),
+    };
+    let same_code = CODE
+        .iter()
+        .all(|&(start, end)| data.get(start..end) == Some(&PROGRAM[start..end]));
+    let mut flow = if same_code { ENTRY } else { Flow::Interpret(0) };
+    loop {
+        flow = match flow {
+            Flow::Block(block) => block(&mut s),
+            Flow::Interpret(pos) => return interpret(s, pos),
+            Flow::Exit => {
+                return Run {
+                    mem: s.mem,
+                    outputs: s.outputs,
+                }
+            }
+        };
+    }
+}
+
+fn interpret(mut s: State, pos: usize) -> Run {
+    let mut machine = Machine::new(&s.mem, &s.inputs[s.next_input..]).with_pos(pos);
+    s.outputs.extend_from_slice(machine.run().outputs());
+    Run {
+        mem: machine.data().to_vec(),
+        outputs: s.outputs,
+    }
+}
+"#;
+
+fn operand(param: Param, raw: i64) -> String {
+    match param {
+        Param::Position => format!("s.mem[{}]", raw as usize),
+        Param::Immediate => raw.to_string(),
+    }
+}
+
+fn goto(analysis: &Analysis, pos: usize) -> String {
+    match analysis.blocks.get(&pos) {
+        Some(block) if !block.instructions.is_empty() => format!("Flow::Block(block_{})", pos),
+        _ => format!("Flow::Interpret({})", pos),
+    }
+}
+
+fn emit_exit(out: &mut String, analysis: &Analysis, exit: Exit) {
+    let flow = match exit {
+        Exit::Next(pos) | Exit::Branch { next: pos, .. } => goto(analysis, pos),
+        Exit::Interpret(pos) => format!("Flow::Interpret({})", pos),
+        Exit::Halt => "Flow::Exit".into(),
+    };
+    writeln!(out, "    {}", flow).unwrap();
+}
+
+fn emit_instruction(out: &mut String, analysis: &Analysis, instruction: &Instruction) {
+    let params = &instruction.command.params;
+    let arg = |i: usize| operand(params[i], instruction.args[i]);
+    writeln!(out, "    // {}: {:?}", instruction.pos, instruction.op()).unwrap();
+    let value = match instruction.op() {
+        Op::Add => format!("{} + {}", arg(0), arg(1)),
+        Op::Multiply => format!("{} * {}", arg(0), arg(1)),
+        Op::Input => "s.input()".into(),
+        Op::LessThan => format!("i64::from({} < {})", arg(0), arg(1)),
+        Op::Equals => format!("i64::from({} == {})", arg(0), arg(1)),
+        Op::Output => {
+            writeln!(out, "    s.outputs.push({});", arg(0)).unwrap();
+            return;
+        }
+        Op::JumpIfTrue | Op::JumpIfFalse => {
+            let cmp = if instruction.op() == Op::JumpIfTrue {
+                "!="
+            } else {
+                "=="
+            };
+            let flow = match instruction.jump_target() {
+                Some(target) => goto(analysis, target),
+                None => format!("dispatch({} as usize)", arg(1)),
+            };
+            writeln!(out, "    if {} {} 0 {{", arg(0), cmp).unwrap();
+            writeln!(out, "        return {};", flow).unwrap();
+            writeln!(out, "    }}").unwrap();
+            return;
+        }
+        Op::Exit => return,
+    };
+    let address = instruction.out_address().unwrap();
+    writeln!(out, "    s.mem[{}] = {};", address, value).unwrap();
+}
+
+fn emit_block(out: &mut String, analysis: &Analysis, block: &Block) {
+    writeln!(out).unwrap();
+    writeln!(out, "fn block_{}(s: &mut State) -> Flow {{", block.start).unwrap();
+    block
+        .instructions
+        .iter()
+        .for_each(|i| emit_instruction(out, analysis, i));
+    emit_exit(out, analysis, block.exit);
+    writeln!(out, "}}").unwrap();
+}
+
+fn emit_dispatch(out: &mut String, analysis: &Analysis) {
+    writeln!(out).unwrap();
+    writeln!(out, "fn dispatch(pos: usize) -> Flow {{").unwrap();
+    writeln!(out, "    match pos {{").unwrap();
+    analysis
+        .blocks
+        .values()
+        .filter(|b| !b.instructions.is_empty())
+        .for_each(|b| {
+            writeln!(
+                out,
+                "        {} => Flow::Block(block_{}),",
+                b.start, b.start
+            )
+            .unwrap()
+        });
+    writeln!(out, "        _ => Flow::Interpret(pos),").unwrap();
+    writeln!(out, "    }}").unwrap();
+    writeln!(out, "}}").unwrap();
+}
+
+fn emit_array<T: ToString>(out: &mut String, name: &str, ty: &str, items: &[T]) {
+    writeln!(out, "const {}: [{}; {}] = [", name, ty, items.len()).unwrap();
+    items.chunks(16).for_each(|chunk| {
+        let line: Vec<_> = chunk.iter().map(ToString::to_string).collect();
+        writeln!(out, "    {},", line.join(", ")).unwrap();
+    });
+    writeln!(out, "];").unwrap();
+}
+
+/// Compiles a program ahead of time into a standalone Rust module.
+///
+/// Each basic block becomes a function that returns the next block to run, so static
+/// jumps are direct calls through a small trampoline in `run`. Jumps through memory go
+/// through a `dispatch` table, and anything the analysis can't vouch for (unknown jump
+/// targets, writes into code, undecodable instructions) hands the current state over
+/// to `Machine` to finish the run. The module exposes `run(inputs)` for the program as
+/// given, `run_with_memory(data, inputs)` for patched copies of it, and
+/// `execute(data, inputs)`, which also returns the final memory.
+pub fn transpile(data: &[i64]) -> String {
+    let analysis = Analysis::new(data);
+    let mut out = String::new();
+    writeln!(out, "//! Ahead-of-time compiled Intcode program.").unwrap();
+    writeln!(out, "//!").unwrap();
+    writeln!(out, "//! Generated by `intcode-transpile`, do not edit.").unwrap();
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "#![allow(clippy::all, dead_code, unreachable_code, unused_variables)]"
+    )
+    .unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "use aoc19::intcode::Machine;").unwrap();
+    writeln!(out).unwrap();
+    emit_array(&mut out, "PROGRAM", "i64", data);
+    writeln!(out).unwrap();
+    let ranges: Vec<_> = analysis
+        .code_ranges()
+        .iter()
+        .map(|(start, end)| format!("({}, {})", start, end))
+        .collect();
+    emit_array(&mut out, "CODE", "(usize, usize)", &ranges);
+    writeln!(out).unwrap();
+    writeln!(out, "const ENTRY: Flow = {};", goto(&analysis, 0)).unwrap();
+    out.push_str(PRELUDE);
+    if analysis.has_dynamic_jumps() {
+        emit_dispatch(&mut out, &analysis);
+    }
+    analysis
+        .blocks
+        .values()
+        .filter(|b| !b.instructions.is_empty())
+        .for_each(|b| emit_block(&mut out, &analysis, b));
+    out
+}
@@ -1,16 +1,50 @@
-use std::collections::BTreeSet;
-use std::fmt::Debug;
-use std::iter::FromIterator;
-use std::str::FromStr;
+#![cfg_attr(not(feature = "std"), no_std)]
 
-pub fn parse_ints<'a, T>(string: &'a str, sep: char) -> impl Iterator<Item = T> + 'a
-where
-    T: FromStr,
-    <T as FromStr>::Err: Debug,
-{
-    string.trim().split(sep).map(|s| s.parse().unwrap())
-}
+extern crate alloc;
+
+use alloc::collections::BTreeSet;
+use core::iter::FromIterator;
+
+#[cfg(feature = "std")]
+mod answers;
+#[cfg(feature = "std")]
+pub mod bench;
+pub mod bigint;
+#[cfg(feature = "std")]
+pub mod days;
+pub mod graph;
+pub mod grid;
+#[cfg(feature = "std")]
+mod input;
+pub mod intcode;
+mod json;
+mod ocr;
+mod parse;
+#[cfg(feature = "std")]
+pub mod sets;
+#[cfg(feature = "std")]
+mod solution;
+
+pub use crate::json::Json;
+pub use crate::ocr::ocr;
+pub use crate::parse::{
+    parse_ints, parse_ints_with, tokenize, try_parse_ints, ParseError, ParseOptions,
+};
+
+#[cfg(feature = "std")]
+pub use crate::answers::{parse_answers, Expected};
+#[cfg(feature = "std")]
+pub use crate::input::{
+    current_profile, get_input, input_dir, input_filename, load_input, profile_path, profiles,
+    Input, Source, DEFAULT_PROFILE, EXAMPLE_PROFILE,
+};
+#[cfg(feature = "std")]
+pub use crate::sets::Occurrences;
+#[cfg(feature = "std")]
+pub use crate::solution::{Answer, Solution};
 
+/// Elements common to two inputs; see [`Occurrences`] for more inputs, positions and
+/// types that are only `Hash + Eq`.
 pub fn get_overlap<'a1, 'a2, T, I1, I2>(iter1: I1, iter2: I2) -> BTreeSet<T>
 where
     T: Clone + Ord + 'a1 + 'a2,
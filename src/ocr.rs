@@ -0,0 +1,88 @@
+//! Recognition of the block letters that some puzzles render as their answer.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+const HEIGHT: usize = 6;
+const CELL: usize = 5;
+
+/// The 6-pixel-high font used by the puzzles; each letter sits in a 5-column cell.
+const GLYPHS: &[(char, [&str; HEIGHT])] = &[
+    ('A', [".##..", "#..#.", "#..#.", "####.", "#..#.", "#..#."]),
+    ('B', ["###..", "#..#.", "###..", "#..#.", "#..#.", "###.."]),
+    ('C', [".##..", "#..#.", "#....", "#....", "#..#.", ".##.."]),
+    ('E', ["####.", "#....", "###..", "#....", "#....", "####."]),
+    ('F', ["####.", "#....", "###..", "#....", "#....", "#...."]),
+    ('G', [".##..", "#..#.", "#....", "#.##.", "#..#.", ".###."]),
+    ('H', ["#..#.", "#..#.", "####.", "#..#.", "#..#.", "#..#."]),
+    ('I', ["###..", ".#...", ".#...", ".#...", ".#...", "###.."]),
+    ('J', ["..##.", "...#.", "...#.", "...#.", "#..#.", ".##.."]),
+    ('K', ["#..#.", "#.#..", "##...", "#.#..", "#.#..", "#..#."]),
+    ('L', ["#....", "#....", "#....", "#....", "#....", "####."]),
+    ('O', [".##..", "#..#.", "#..#.", "#..#.", "#..#.", ".##.."]),
+    ('P', ["###..", "#..#.", "#..#.", "###..", "#....", "#...."]),
+    ('R', ["###..", "#..#.", "#..#.", "###..", "#.#..", "#..#."]),
+    ('S', [".###.", "#....", "#....", ".##..", "...#.", "###.."]),
+    ('U', ["#..#.", "#..#.", "#..#.", "#..#.", "#..#.", ".##.."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####.", "...#.", "..#..", ".#...", "#....", "####."]),
+];
+
+fn is_lit(c: char) -> bool {
+    !c.is_whitespace() && c != '.' && c != '0'
+}
+
+fn matches(rows: &[&[bool]], col: usize, glyph: &[&str; HEIGHT]) -> bool {
+    rows.iter().zip(glyph.iter()).all(|(row, pattern)| {
+        pattern
+            .chars()
+            .enumerate()
+            .all(|(i, c)| row.get(col + i).cloned().unwrap_or(false) == (c == '#'))
+    })
+}
+
+/// Reads letters off a rendered image, one row per string. Any character other
+/// than whitespace, `.` or `0` counts as a lit pixel. Returns `None` if the image
+/// isn't six rows high or if any cell doesn't match a known letter.
+pub fn ocr<S: AsRef<str>>(rows: &[S]) -> Option<String> {
+    if rows.len() != HEIGHT {
+        return None;
+    }
+    let pixels: Vec<Vec<bool>> = rows
+        .iter()
+        .map(|row| row.as_ref().chars().map(is_lit).collect())
+        .collect();
+    let rows: Vec<&[bool]> = pixels.iter().map(|row| &row[..]).collect();
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    if width == 0 {
+        return None;
+    }
+    (0..width)
+        .step_by(CELL)
+        .map(|col| {
+            GLYPHS
+                .iter()
+                .find(|(_, glyph)| matches(&rows, col, glyph))
+                .map(|&(letter, _)| letter)
+        })
+        .collect()
+}
+
+#[test]
+fn test_ocr() {
+    let rows = [
+        "#..#.####.#....#.....##..",
+        "#..#.#....#....#....#..#.",
+        "####.###..#....#....#..#.",
+        "#..#.#....#....#....#..#.",
+        "#..#.#....#....#....#..#.",
+        "#..#.####.####.####..##..",
+    ];
+    assert_eq!(ocr(&rows).as_deref(), Some("HELLO"));
+    let spaces: Vec<String> = rows.iter().map(|r| r.replace('.', " ")).collect();
+    assert_eq!(ocr(&spaces).as_deref(), Some("HELLO"));
+    let mut broken = rows;
+    broken[0] = "##.#.####.#....#.....##..";
+    assert_eq!(ocr(&broken), None);
+    assert_eq!(ocr(&rows[..5]), None);
+}
@@ -0,0 +1,191 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display};
+use core::str::FromStr;
+
+/// What `parse_ints_with` forgives in its input.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Strip a `\r` left at the end of a token by Windows line endings.
+    pub crlf: bool,
+    /// Ignore an empty token after a trailing separator.
+    pub trailing_sep: bool,
+    /// Trim whitespace around every token.
+    pub trim_tokens: bool,
+}
+
+impl ParseOptions {
+    pub fn strict() -> Self {
+        Self {
+            crlf: false,
+            trailing_sep: false,
+            trim_tokens: false,
+        }
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            crlf: true,
+            trailing_sep: true,
+            trim_tokens: true,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub token: String,
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+    pub reason: String,
+    /// Further errors found in the same input, reported after this one.
+    pub more: Vec<ParseError>,
+}
+
+impl ParseError {
+    /// Locates `token` at byte `offset` within `string`; lines and columns start at 1.
+    pub fn new(string: &str, offset: usize, token: &str, reason: impl Display) -> Self {
+        let before = &string[..offset];
+        let line = 1 + before.matches('\n').count();
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = 1 + before[line_start..].chars().count();
+        Self {
+            token: token.into(),
+            offset,
+            line,
+            column,
+            reason: reason.to_string(),
+            more: Vec::new(),
+        }
+    }
+
+    /// Reports several errors as one, in the order given; `None` if there are none.
+    pub fn combine(errors: impl IntoIterator<Item = ParseError>) -> Option<Self> {
+        let mut errors = errors.into_iter();
+        let mut first = errors.next()?;
+        first.more.extend(errors);
+        Some(first)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid token {:?} at line {}, column {} (byte {}): {}",
+            self.token, self.line, self.column, self.offset, self.reason
+        )?;
+        self.more.iter().try_for_each(|err| write!(f, "\n{}", err))
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Splits `string` on `sep` into tokens paired with their byte offsets.
+pub fn tokenize<'a>(
+    string: &'a str,
+    sep: char,
+    options: ParseOptions,
+) -> impl Iterator<Item = (usize, &'a str)> + 'a {
+    let start = string.len() - string.trim_start().len();
+    let body = string.trim();
+    let n_tokens = body.split(sep).count();
+    body.split(sep)
+        .scan(start, move |offset, token| {
+            let token_offset = *offset;
+            *offset += token.len() + sep.len_utf8();
+            Some((token_offset, token))
+        })
+        .enumerate()
+        .filter_map(move |(i, (mut offset, mut token))| {
+            if options.crlf {
+                token = token.strip_suffix('\r').unwrap_or(token);
+            }
+            if options.trim_tokens {
+                offset += token.len() - token.trim_start().len();
+                token = token.trim();
+            }
+            if options.trailing_sep && i + 1 == n_tokens && i > 0 && token.is_empty() {
+                None
+            } else {
+                Some((offset, token))
+            }
+        })
+}
+
+/// Parses `sep`-separated values, reporting the first one that fails with its location.
+pub fn parse_ints_with<T>(
+    string: &str,
+    sep: char,
+    options: ParseOptions,
+) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Display,
+{
+    tokenize(string, sep, options)
+        .map(|(offset, token)| {
+            token
+                .parse()
+                .map_err(|err| ParseError::new(string, offset, token, err))
+        })
+        .collect()
+}
+
+/// Same as `parse_ints_with` using the lenient default options.
+pub fn try_parse_ints<T>(string: &str, sep: char) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Display,
+{
+    parse_ints_with(string, sep, ParseOptions::default())
+}
+
+pub fn parse_ints<'a, T>(string: &'a str, sep: char) -> impl Iterator<Item = T> + 'a
+where
+    T: FromStr,
+    <T as FromStr>::Err: Debug,
+{
+    tokenize(string, sep, ParseOptions::strict()).map(move |(offset, token)| {
+        token.parse().unwrap_or_else(|err| {
+            panic!(
+                "{}",
+                ParseError::new(string, offset, token, format_args!("{:?}", err))
+            )
+        })
+    })
+}
+
+#[test]
+fn test_parse_ints_with() {
+    use alloc::vec;
+
+    let parsed: Result<Vec<i64>, _> = try_parse_ints(" 1, 2 ,-3,\r\n", ',');
+    assert_eq!(parsed, Ok(vec![1, 2, -3]));
+    let parsed: Result<Vec<i64>, _> = try_parse_ints("12\r\n34\r\n", '\n');
+    assert_eq!(parsed, Ok(vec![12, 34]));
+
+    let err = parse_ints_with::<i64>("1,2,\n3, 4", ',', ParseOptions::strict()).unwrap_err();
+    assert_eq!(
+        (err.token.as_str(), err.offset, err.line, err.column),
+        ("\n3", 4, 1, 5)
+    );
+    let err = try_parse_ints::<i64>("1\n2\n\n4", '\n').unwrap_err();
+    assert_eq!(
+        (err.token.as_str(), err.offset, err.line, err.column),
+        ("", 4, 3, 1)
+    );
+    let err = try_parse_ints::<u8>("10,20,\n  300,40", ',').unwrap_err();
+    assert_eq!(
+        (err.token.as_str(), err.offset, err.line, err.column),
+        ("300", 9, 2, 3)
+    );
+    assert_eq!(
+        err.to_string(),
+        "invalid token \"300\" at line 2, column 3 (byte 9): number too large to fit in target type"
+    );
+}
@@ -0,0 +1,116 @@
+//! Set operations over any number of inputs, for elements that are only `Hash + Eq`.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Positions at which each distinct element occurs in each of several inputs.
+///
+/// Iteration order over elements is unspecified.
+#[derive(Clone, Debug)]
+pub struct Occurrences<T> {
+    n_inputs: usize,
+    positions: HashMap<T, Vec<Vec<usize>>>,
+}
+
+impl<T: Hash + Eq> Occurrences<T> {
+    pub fn new<I, J>(inputs: I) -> Self
+    where
+        I: IntoIterator<Item = J>,
+        J: IntoIterator<Item = T>,
+    {
+        let mut positions = HashMap::<T, Vec<Vec<usize>>>::new();
+        let mut n_inputs = 0;
+        for (input, items) in inputs.into_iter().enumerate() {
+            n_inputs = input + 1;
+            for (pos, item) in items.into_iter().enumerate() {
+                let entry = positions.entry(item).or_default();
+                entry.resize(n_inputs, Vec::new());
+                entry[input].push(pos);
+            }
+        }
+        positions
+            .values_mut()
+            .for_each(|p| p.resize(n_inputs, Vec::new()));
+        Self {
+            n_inputs,
+            positions,
+        }
+    }
+
+    pub fn n_inputs(&self) -> usize {
+        self.n_inputs
+    }
+
+    /// Positions of an element in every input, empty where it doesn't occur.
+    pub fn positions(&self, item: &T) -> Option<&[Vec<usize>]> {
+        self.positions.get(item).map(Vec::as_slice)
+    }
+
+    /// Number of times an element occurs in each input.
+    pub fn multiplicities(&self, item: &T) -> Vec<usize> {
+        match self.positions(item) {
+            Some(positions) => positions.iter().map(Vec::len).collect(),
+            None => vec![0; self.n_inputs],
+        }
+    }
+
+    /// Elements occurring in at least `k` of the inputs, with their positions.
+    pub fn in_at_least(&self, k: usize) -> impl Iterator<Item = (&T, &[Vec<usize>])> {
+        self.positions
+            .iter()
+            .filter(move |(_, p)| p.iter().filter(|p| !p.is_empty()).count() >= k)
+            .map(|(item, p)| (item, p.as_slice()))
+    }
+
+    /// Elements occurring in every input.
+    pub fn intersection(&self) -> impl Iterator<Item = (&T, &[Vec<usize>])> {
+        self.in_at_least(self.n_inputs.max(1))
+    }
+
+    /// Elements occurring in any input.
+    pub fn union(&self) -> impl Iterator<Item = (&T, &[Vec<usize>])> {
+        self.in_at_least(0)
+    }
+
+    /// Elements occurring in the given input and in none of the others.
+    pub fn difference(&self, input: usize) -> impl Iterator<Item = (&T, &[usize])> {
+        self.positions.iter().filter_map(move |(item, p)| {
+            let only_here = p
+                .iter()
+                .enumerate()
+                .all(|(i, p)| (i == input) != p.is_empty());
+            if only_here {
+                Some((item, p[input].as_slice()))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+#[test]
+fn test_occurrences() {
+    let sorted = |iter: &mut dyn Iterator<Item = &char>| {
+        let mut items: Vec<_> = iter.cloned().collect();
+        items.sort_unstable();
+        items.into_iter().collect::<String>()
+    };
+    let occ = Occurrences::new(vec!["abcab".chars(), "bcd".chars(), "cbbe".chars()]);
+    assert_eq!(occ.n_inputs(), 3);
+    assert_eq!(sorted(&mut occ.intersection().map(|(c, _)| c)), "bc");
+    assert_eq!(sorted(&mut occ.union().map(|(c, _)| c)), "abcde");
+    assert_eq!(sorted(&mut occ.in_at_least(2).map(|(c, _)| c)), "bc");
+    assert_eq!(sorted(&mut occ.difference(0).map(|(c, _)| c)), "a");
+    assert_eq!(sorted(&mut occ.difference(2).map(|(c, _)| c)), "e");
+    let b: &[Vec<usize>] = &[vec![1, 4], vec![0], vec![1, 2]];
+    assert_eq!(occ.positions(&'b'), Some(b));
+    assert_eq!(occ.positions(&'a').unwrap()[1], []);
+    assert_eq!(occ.multiplicities(&'a'), [2, 0, 0]);
+    assert_eq!(occ.multiplicities(&'z'), [0, 0, 0]);
+    assert_eq!(
+        Occurrences::<u8>::new(Vec::<Vec<u8>>::new())
+            .union()
+            .count(),
+        0
+    );
+}
@@ -0,0 +1,90 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::{ocr, Json, ParseError};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    Text(String),
+    /// Rows of a rendered image whose letters are the actual answer.
+    Image(Vec<String>),
+}
+
+impl Answer {
+    /// Letters read off an image answer, if every one of them is recognized.
+    pub fn letters(&self) -> Option<String> {
+        match self {
+            Answer::Image(rows) => ocr(rows),
+            _ => None,
+        }
+    }
+
+    /// Numbers stay numbers; an image becomes its letters where they can be
+    /// read and its rows joined by newlines otherwise.
+    pub fn to_json(&self) -> Json {
+        match self {
+            Answer::Int(value) => Json::Int(*value),
+            Answer::Text(value) => value.trim_end().into(),
+            Answer::Image(_) => self.letters().unwrap_or_else(|| self.to_string()).into(),
+        }
+    }
+}
+
+macro_rules! impl_answer_from_int {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Answer {
+                fn from(value: $ty) -> Self {
+                    Answer::Int(value as _)
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_from_int!(i32, i64, u32, usize);
+
+/// Counts too large for an `Int` are kept as text.
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        i64::try_from(value).map_or_else(|_| Answer::Text(value.to_string()), Answer::Int)
+    }
+}
+
+/// Totals too large for an `Int` are kept as text.
+impl From<i128> for Answer {
+    fn from(value: i128) -> Self {
+        i64::try_from(value).map_or_else(|_| Answer::Text(value.to_string()), Answer::Int)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value.trim_end()),
+            Answer::Image(rows) => write!(f, "{}", rows.join("\n").trim_end()),
+        }
+    }
+}
+
+/// A day's puzzle: its input is parsed once and shared by both parts.
+pub trait Solution {
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input) -> Answer;
+
+    /// Left as `None` by days whose second part isn't solved.
+    fn part2(_input: &Self::Input) -> Option<Answer> {
+        None
+    }
+}
@@ -0,0 +1,63 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use aoc19::days::{get_day, DAYS};
+use aoc19::{parse_answers, Expected};
+
+fn load_manifest() -> Vec<Expected> {
+    parse_answers(include_str!("../src/bin/inputs/answers.txt")).unwrap()
+}
+
+fn load_input(expected: &Expected) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src/bin/inputs")
+        .join(expected.input_path());
+    fs::read_to_string(&path).unwrap_or_else(|err| panic!("{}: {}", path.display(), err))
+}
+
+#[test]
+fn test_known_answers() {
+    let failures: Vec<_> = load_manifest()
+        .iter()
+        .filter_map(|expected| {
+            let day = get_day(expected.day).expect("unknown day in manifest");
+            let run = (day.solve)(&load_input(expected), &[expected.part]).unwrap();
+            let answer = run.answer(expected.part).map(ToString::to_string);
+            if answer.as_ref() == Some(&expected.answer) {
+                None
+            } else {
+                Some(format!("{}: got {:?}", expected, answer))
+            }
+        })
+        .collect();
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn test_all_parts_covered() {
+    let covered: BTreeSet<_> = load_manifest()
+        .iter()
+        .filter(|expected| expected.set == "default")
+        .map(|expected| (expected.day, expected.part))
+        .collect();
+    for day in DAYS {
+        for run in (day.solve)(day.bundled, &[1, 2]).unwrap().parts {
+            assert!(
+                covered.contains(&(day.day, run.part)),
+                "no known answer for day {} part {}",
+                day.day,
+                run.part
+            );
+        }
+    }
+}
+
+#[test]
+fn test_image_letters() {
+    let day = get_day(8).unwrap();
+    let run = (day.solve)(day.bundled, &[2]).unwrap();
+    let answer = run.answer(2).unwrap();
+    assert_eq!(answer.letters().as_deref(), Some("FKAHL"));
+    assert_eq!(answer.to_json().to_string(), "\"FKAHL\"");
+}
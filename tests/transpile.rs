@@ -0,0 +1,69 @@
+use aoc19::intcode::{transpile, Machine};
+use aoc19::parse_ints;
+
+#[rustfmt::skip]
+#[path = "transpiled/compare.rs"]
+mod compare;
+#[rustfmt::skip]
+#[path = "transpiled/day02.rs"]
+mod day02;
+#[rustfmt::skip]
+#[path = "transpiled/day07.rs"]
+mod day07;
+
+const COMPARE: &str = "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,1106,0,36,98,0,0,\
+                       1002,21,125,20,4,20,1105,1,46,104,999,1105,1,46,1101,1000,1,20,4,20,\
+                       1105,1,46,98,99";
+const DAY02: &str = include_str!("../src/bin/inputs/day-02.txt");
+const DAY07: &str = include_str!("../src/bin/inputs/day-07.txt");
+
+fn check_generated(program: &str, generated: &str) -> Vec<i64> {
+    let data: Vec<i64> = parse_ints(program, ',').collect();
+    assert_eq!(
+        transpile(&data),
+        generated,
+        "stale generated code, rerun `intcode-transpile`"
+    );
+    data
+}
+
+#[test]
+fn test_compare() {
+    let data = check_generated(COMPARE, include_str!("transpiled/compare.rs"));
+    for input in -5..20 {
+        let expected = Machine::new(&data, &[input]).run().outputs().to_vec();
+        assert_eq!(compare::run(&[input]), expected);
+    }
+    let mut patched = data.clone();
+    patched[4] = 9;
+    let expected = Machine::new(&patched, &[9]).run().outputs().to_vec();
+    assert_eq!(compare::run_with_memory(&patched, &[9]), expected);
+}
+
+#[test]
+fn test_day02() {
+    let data = check_generated(DAY02, include_str!("transpiled/day02.rs"));
+    let output = |noun, verb| {
+        let mut patched = data.clone();
+        patched[1] = noun;
+        patched[2] = verb;
+        day02::execute(&patched, &[]).mem[0]
+    };
+    assert_eq!(output(12, 2), 5305097);
+    let found = (0..100)
+        .flat_map(|noun| (0..100).map(move |verb| (noun, verb)))
+        .find(|&(noun, verb)| output(noun, verb) == 19690720);
+    assert_eq!(found, Some((49, 25)));
+}
+
+#[test]
+fn test_day07() {
+    let data = check_generated(DAY07, include_str!("transpiled/day07.rs"));
+    for phase in 0..5 {
+        for signal in &[0, 1, 17, 12345] {
+            let inputs = [phase, *signal];
+            let expected = Machine::new(&data, &inputs).run().outputs().to_vec();
+            assert_eq!(day07::run(&inputs), expected);
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Ahead-of-time compiled Intcode program.
+//!
+//! Generated by `intcode-transpile`, do not edit.
+
+#![allow(clippy::all, dead_code, unreachable_code, unused_variables)]
+
+use aoc19::intcode::Machine;
+
+const PROGRAM: [i64; 47] = [
+    3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31,
+    1106, 0, 36, 98, 0, 0, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104,
+    999, 1105, 1, 46, 1101, 1000, 1, 20, 4, 20, 1105, 1, 46, 98, 99,
+];
+
+const CODE: [(usize, usize); 3] = [
+    (0, 19), (22, 45), (46, 47),
+];
+
+const ENTRY: Flow = Flow::Block(block_0);
+
+enum Flow {
+    Block(fn(&mut State) -> Flow),
+    Interpret(usize),
+    Exit,
+}
+
+struct State {
+    mem: Vec<i64>,
+    inputs: Vec<i64>,
+    next_input: usize,
+    outputs: Vec<i64>,
+}
+
+impl State {
+    fn input(&mut self) -> i64 {
+        let value = *self.inputs.get(self.next_input).expect("no input available");
+        self.next_input += 1;
+        value
+    }
+}
+
+/// Final memory of a run along with everything the program printed.
+pub struct Run {
+    pub mem: Vec<i64>,
+    pub outputs: Vec<i64>,
+}
+
+pub fn run(inputs: &[i64]) -> Vec<i64> {
+    execute(&PROGRAM, inputs).outputs
+}
+
+pub fn run_with_memory(data: &[i64], inputs: &[i64]) -> Vec<i64> {
+    execute(data, inputs).outputs
+}
+
+/// Runs a copy of `data`, keeping the final memory for programs that leave their
+/// answer there.
+pub fn execute(data: &[i64], inputs: &[i64]) -> Run {
+    let mut s = State {
+        mem: data.to_vec(),
+        inputs: inputs.to_vec(),
+        next_input: 0,
+        outputs: Vec::new(),
+    };
+    let same_code = CODE
+        .iter()
+        .all(|&(start, end)| data.get(start..end) == Some(&PROGRAM[start..end]));
+    let mut flow = if same_code { ENTRY } else { Flow::Interpret(0) };
+    loop {
+        flow = match flow {
+            Flow::Block(block) => block(&mut s),
+            Flow::Interpret(pos) => return interpret(s, pos),
+            Flow::Exit => {
+                return Run {
+                    mem: s.mem,
+                    outputs: s.outputs,
+                }
+            }
+        };
+    }
+}
+
+fn interpret(mut s: State, pos: usize) -> Run {
+    let mut machine = Machine::new(&s.mem, &s.inputs[s.next_input..]).with_pos(pos);
+    s.outputs.extend_from_slice(machine.run().outputs());
+    Run {
+        mem: machine.data().to_vec(),
+        outputs: s.outputs,
+    }
+}
+
+fn block_0(s: &mut State) -> Flow {
+    // 0: Input
+    s.mem[21] = s.input();
+    // 2: Equals
+    s.mem[20] = i64::from(s.mem[21] == 8);
+    // 6: JumpIfTrue
+    if s.mem[20] != 0 {
+        return Flow::Block(block_22);
+    }
+    Flow::Block(block_9)
+}
+
+fn block_9(s: &mut State) -> Flow {
+    // 9: LessThan
+    s.mem[20] = i64::from(8 < s.mem[21]);
+    // 13: JumpIfFalse
+    if s.mem[20] == 0 {
+        return Flow::Block(block_31);
+    }
+    Flow::Block(block_16)
+}
+
+fn block_16(s: &mut State) -> Flow {
+    // 16: JumpIfFalse
+    if 0 == 0 {
+        return Flow::Block(block_36);
+    }
+    Flow::Interpret(19)
+}
+
+fn block_22(s: &mut State) -> Flow {
+    // 22: Multiply
+    s.mem[20] = s.mem[21] * 125;
+    // 26: Output
+    s.outputs.push(s.mem[20]);
+    // 28: JumpIfTrue
+    if 1 != 0 {
+        return Flow::Block(block_46);
+    }
+    Flow::Block(block_31)
+}
+
+fn block_31(s: &mut State) -> Flow {
+    // 31: Output
+    s.outputs.push(999);
+    // 33: JumpIfTrue
+    if 1 != 0 {
+        return Flow::Block(block_46);
+    }
+    Flow::Block(block_36)
+}
+
+fn block_36(s: &mut State) -> Flow {
+    // 36: Add
+    s.mem[20] = 1000 + 1;
+    // 40: Output
+    s.outputs.push(s.mem[20]);
+    // 42: JumpIfTrue
+    if 1 != 0 {
+        return Flow::Block(block_46);
+    }
+    Flow::Interpret(45)
+}
+
+fn block_46(s: &mut State) -> Flow {
+    // 46: Exit
+    Flow::Exit
+}
@@ -0,0 +1,330 @@
+//! Ahead-of-time compiled Intcode program.
+//!
+//! Generated by `intcode-transpile`, do not edit.
+
+#![allow(clippy::all, dead_code, unreachable_code, unused_variables)]
+
+use aoc19::intcode::Machine;
+
+const PROGRAM: [i64; 157] = [
+    1, 0, 0, 3, 1, 1, 2, 3, 1, 3, 4, 3, 1, 5, 0, 3,
+    2, 1, 13, 19, 1, 9, 19, 23, 1, 6, 23, 27, 2, 27, 9, 31,
+    2, 6, 31, 35, 1, 5, 35, 39, 1, 10, 39, 43, 1, 43, 13, 47,
+    1, 47, 9, 51, 1, 51, 9, 55, 1, 55, 9, 59, 2, 9, 59, 63,
+    2, 9, 63, 67, 1, 5, 67, 71, 2, 13, 71, 75, 1, 6, 75, 79,
+    1, 10, 79, 83, 2, 6, 83, 87, 1, 87, 5, 91, 1, 91, 9, 95,
+    1, 95, 10, 99, 2, 9, 99, 103, 1, 5, 103, 107, 1, 5, 107, 111,
+    2, 111, 10, 115, 1, 6, 115, 119, 2, 10, 119, 123, 1, 6, 123, 127,
+    1, 127, 5, 131, 2, 9, 131, 135, 1, 5, 135, 139, 1, 139, 10, 143,
+    1, 143, 2, 147, 1, 147, 5, 0, 99, 2, 0, 14, 0,
+];
+
+const CODE: [(usize, usize); 1] = [
+    (0, 153),
+];
+
+const ENTRY: Flow = Flow::Block(block_0);
+
+enum Flow {
+    Block(fn(&mut State) -> Flow),
+    Interpret(usize),
+    Exit,
+}
+
+struct State {
+    mem: Vec<i64>,
+    inputs: Vec<i64>,
+    next_input: usize,
+    outputs: Vec<i64>,
+}
+
+impl State {
+    fn input(&mut self) -> i64 {
+        let value = *self.inputs.get(self.next_input).expect("no input available");
+        self.next_input += 1;
+        value
+    }
+}
+
+/// Final memory of a run along with everything the program printed.
+pub struct Run {
+    pub mem: Vec<i64>,
+    pub outputs: Vec<i64>,
+}
+
+pub fn run(inputs: &[i64]) -> Vec<i64> {
+    execute(&PROGRAM, inputs).outputs
+}
+
+pub fn run_with_memory(data: &[i64], inputs: &[i64]) -> Vec<i64> {
+    execute(data, inputs).outputs
+}
+
+/// Runs a copy of `data`, keeping the final memory for programs that leave their
+/// answer there.
+pub fn execute(data: &[i64], inputs: &[i64]) -> Run {
+    let mut s = State {
+        mem: data.to_vec(),
+        inputs: inputs.to_vec(),
+        next_input: 0,
+        outputs: Vec::new(),
+    };
+    let same_code = CODE
+        .iter()
+        .all(|&(start, end)| data.get(start..end) == Some(&PROGRAM[start..end]));
+    let mut flow = if same_code { ENTRY } else { Flow::Interpret(0) };
+    loop {
+        flow = match flow {
+            Flow::Block(block) => block(&mut s),
+            Flow::Interpret(pos) => return interpret(s, pos),
+            Flow::Exit => {
+                return Run {
+                    mem: s.mem,
+                    outputs: s.outputs,
+                }
+            }
+        };
+    }
+}
+
+fn interpret(mut s: State, pos: usize) -> Run {
+    let mut machine = Machine::new(&s.mem, &s.inputs[s.next_input..]).with_pos(pos);
+    s.outputs.extend_from_slice(machine.run().outputs());
+    Run {
+        mem: machine.data().to_vec(),
+        outputs: s.outputs,
+    }
+}
+
+fn block_0(s: &mut State) -> Flow {
+    // 0: Add
+    s.mem[3] = s.mem[0] + s.mem[0];
+    Flow::Interpret(4)
+}
+
+fn block_4(s: &mut State) -> Flow {
+    // 4: Add
+    s.mem[3] = s.mem[1] + s.mem[2];
+    Flow::Interpret(8)
+}
+
+fn block_8(s: &mut State) -> Flow {
+    // 8: Add
+    s.mem[3] = s.mem[3] + s.mem[4];
+    Flow::Interpret(12)
+}
+
+fn block_12(s: &mut State) -> Flow {
+    // 12: Add
+    s.mem[3] = s.mem[5] + s.mem[0];
+    Flow::Interpret(16)
+}
+
+fn block_16(s: &mut State) -> Flow {
+    // 16: Multiply
+    s.mem[19] = s.mem[1] * s.mem[13];
+    Flow::Interpret(20)
+}
+
+fn block_20(s: &mut State) -> Flow {
+    // 20: Add
+    s.mem[23] = s.mem[9] + s.mem[19];
+    Flow::Interpret(24)
+}
+
+fn block_24(s: &mut State) -> Flow {
+    // 24: Add
+    s.mem[27] = s.mem[6] + s.mem[23];
+    Flow::Interpret(28)
+}
+
+fn block_28(s: &mut State) -> Flow {
+    // 28: Multiply
+    s.mem[31] = s.mem[27] * s.mem[9];
+    Flow::Interpret(32)
+}
+
+fn block_32(s: &mut State) -> Flow {
+    // 32: Multiply
+    s.mem[35] = s.mem[6] * s.mem[31];
+    Flow::Interpret(36)
+}
+
+fn block_36(s: &mut State) -> Flow {
+    // 36: Add
+    s.mem[39] = s.mem[5] + s.mem[35];
+    Flow::Interpret(40)
+}
+
+fn block_40(s: &mut State) -> Flow {
+    // 40: Add
+    s.mem[43] = s.mem[10] + s.mem[39];
+    Flow::Interpret(44)
+}
+
+fn block_44(s: &mut State) -> Flow {
+    // 44: Add
+    s.mem[47] = s.mem[43] + s.mem[13];
+    Flow::Interpret(48)
+}
+
+fn block_48(s: &mut State) -> Flow {
+    // 48: Add
+    s.mem[51] = s.mem[47] + s.mem[9];
+    Flow::Interpret(52)
+}
+
+fn block_52(s: &mut State) -> Flow {
+    // 52: Add
+    s.mem[55] = s.mem[51] + s.mem[9];
+    Flow::Interpret(56)
+}
+
+fn block_56(s: &mut State) -> Flow {
+    // 56: Add
+    s.mem[59] = s.mem[55] + s.mem[9];
+    Flow::Interpret(60)
+}
+
+fn block_60(s: &mut State) -> Flow {
+    // 60: Multiply
+    s.mem[63] = s.mem[9] * s.mem[59];
+    Flow::Interpret(64)
+}
+
+fn block_64(s: &mut State) -> Flow {
+    // 64: Multiply
+    s.mem[67] = s.mem[9] * s.mem[63];
+    Flow::Interpret(68)
+}
+
+fn block_68(s: &mut State) -> Flow {
+    // 68: Add
+    s.mem[71] = s.mem[5] + s.mem[67];
+    Flow::Interpret(72)
+}
+
+fn block_72(s: &mut State) -> Flow {
+    // 72: Multiply
+    s.mem[75] = s.mem[13] * s.mem[71];
+    Flow::Interpret(76)
+}
+
+fn block_76(s: &mut State) -> Flow {
+    // 76: Add
+    s.mem[79] = s.mem[6] + s.mem[75];
+    Flow::Interpret(80)
+}
+
+fn block_80(s: &mut State) -> Flow {
+    // 80: Add
+    s.mem[83] = s.mem[10] + s.mem[79];
+    Flow::Interpret(84)
+}
+
+fn block_84(s: &mut State) -> Flow {
+    // 84: Multiply
+    s.mem[87] = s.mem[6] * s.mem[83];
+    Flow::Interpret(88)
+}
+
+fn block_88(s: &mut State) -> Flow {
+    // 88: Add
+    s.mem[91] = s.mem[87] + s.mem[5];
+    Flow::Interpret(92)
+}
+
+fn block_92(s: &mut State) -> Flow {
+    // 92: Add
+    s.mem[95] = s.mem[91] + s.mem[9];
+    Flow::Interpret(96)
+}
+
+fn block_96(s: &mut State) -> Flow {
+    // 96: Add
+    s.mem[99] = s.mem[95] + s.mem[10];
+    Flow::Interpret(100)
+}
+
+fn block_100(s: &mut State) -> Flow {
+    // 100: Multiply
+    s.mem[103] = s.mem[9] * s.mem[99];
+    Flow::Interpret(104)
+}
+
+fn block_104(s: &mut State) -> Flow {
+    // 104: Add
+    s.mem[107] = s.mem[5] + s.mem[103];
+    Flow::Interpret(108)
+}
+
+fn block_108(s: &mut State) -> Flow {
+    // 108: Add
+    s.mem[111] = s.mem[5] + s.mem[107];
+    Flow::Interpret(112)
+}
+
+fn block_112(s: &mut State) -> Flow {
+    // 112: Multiply
+    s.mem[115] = s.mem[111] * s.mem[10];
+    Flow::Interpret(116)
+}
+
+fn block_116(s: &mut State) -> Flow {
+    // 116: Add
+    s.mem[119] = s.mem[6] + s.mem[115];
+    Flow::Interpret(120)
+}
+
+fn block_120(s: &mut State) -> Flow {
+    // 120: Multiply
+    s.mem[123] = s.mem[10] * s.mem[119];
+    Flow::Interpret(124)
+}
+
+fn block_124(s: &mut State) -> Flow {
+    // 124: Add
+    s.mem[127] = s.mem[6] + s.mem[123];
+    Flow::Interpret(128)
+}
+
+fn block_128(s: &mut State) -> Flow {
+    // 128: Add
+    s.mem[131] = s.mem[127] + s.mem[5];
+    Flow::Interpret(132)
+}
+
+fn block_132(s: &mut State) -> Flow {
+    // 132: Multiply
+    s.mem[135] = s.mem[9] * s.mem[131];
+    Flow::Interpret(136)
+}
+
+fn block_136(s: &mut State) -> Flow {
+    // 136: Add
+    s.mem[139] = s.mem[5] + s.mem[135];
+    Flow::Interpret(140)
+}
+
+fn block_140(s: &mut State) -> Flow {
+    // 140: Add
+    s.mem[143] = s.mem[139] + s.mem[10];
+    Flow::Interpret(144)
+}
+
+fn block_144(s: &mut State) -> Flow {
+    // 144: Add
+    s.mem[147] = s.mem[143] + s.mem[2];
+    Flow::Interpret(148)
+}
+
+fn block_148(s: &mut State) -> Flow {
+    // 148: Add
+    s.mem[0] = s.mem[147] + s.mem[5];
+    Flow::Interpret(152)
+}
+
+fn block_152(s: &mut State) -> Flow {
+    // 152: Exit
+    Flow::Exit
+}
@@ -0,0 +1,149 @@
+//! Ahead-of-time compiled Intcode program.
+//!
+//! Generated by `intcode-transpile`, do not edit.
+
+#![allow(clippy::all, dead_code, unreachable_code, unused_variables)]
+
+use aoc19::intcode::Machine;
+
+const PROGRAM: [i64; 515] = [
+    3, 8, 1001, 8, 10, 8, 105, 1, 0, 0, 21, 46, 55, 72, 85, 110,
+    191, 272, 353, 434, 99999, 3, 9, 1002, 9, 5, 9, 1001, 9, 2, 9, 102,
+    3, 9, 9, 101, 2, 9, 9, 102, 4, 9, 9, 4, 9, 99, 3, 9,
+    102, 5, 9, 9, 4, 9, 99, 3, 9, 1002, 9, 2, 9, 101, 2, 9,
+    9, 1002, 9, 2, 9, 4, 9, 99, 3, 9, 1002, 9, 4, 9, 101, 3,
+    9, 9, 4, 9, 99, 3, 9, 1002, 9, 3, 9, 101, 5, 9, 9, 1002,
+    9, 3, 9, 101, 3, 9, 9, 1002, 9, 5, 9, 4, 9, 99, 3, 9,
+    1001, 9, 2, 9, 4, 9, 3, 9, 101, 2, 9, 9, 4, 9, 3, 9,
+    101, 2, 9, 9, 4, 9, 3, 9, 1001, 9, 2, 9, 4, 9, 3, 9,
+    102, 2, 9, 9, 4, 9, 3, 9, 102, 2, 9, 9, 4, 9, 3, 9,
+    102, 2, 9, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4, 9, 3, 9,
+    102, 2, 9, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4, 9, 99, 3,
+    9, 102, 2, 9, 9, 4, 9, 3, 9, 102, 2, 9, 9, 4, 9, 3,
+    9, 1001, 9, 1, 9, 4, 9, 3, 9, 1001, 9, 1, 9, 4, 9, 3,
+    9, 1002, 9, 2, 9, 4, 9, 3, 9, 1001, 9, 2, 9, 4, 9, 3,
+    9, 102, 2, 9, 9, 4, 9, 3, 9, 101, 1, 9, 9, 4, 9, 3,
+    9, 1001, 9, 1, 9, 4, 9, 3, 9, 101, 2, 9, 9, 4, 9, 99,
+    3, 9, 1002, 9, 2, 9, 4, 9, 3, 9, 1001, 9, 2, 9, 4, 9,
+    3, 9, 101, 2, 9, 9, 4, 9, 3, 9, 1001, 9, 1, 9, 4, 9,
+    3, 9, 1002, 9, 2, 9, 4, 9, 3, 9, 1001, 9, 1, 9, 4, 9,
+    3, 9, 1001, 9, 1, 9, 4, 9, 3, 9, 1001, 9, 2, 9, 4, 9,
+    3, 9, 1001, 9, 1, 9, 4, 9, 3, 9, 101, 2, 9, 9, 4, 9,
+    99, 3, 9, 1001, 9, 2, 9, 4, 9, 3, 9, 102, 2, 9, 9, 4,
+    9, 3, 9, 101, 2, 9, 9, 4, 9, 3, 9, 1001, 9, 2, 9, 4,
+    9, 3, 9, 1001, 9, 2, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4,
+    9, 3, 9, 102, 2, 9, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4,
+    9, 3, 9, 1002, 9, 2, 9, 4, 9, 3, 9, 1001, 9, 1, 9, 4,
+    9, 99, 3, 9, 101, 1, 9, 9, 4, 9, 3, 9, 102, 2, 9, 9,
+    4, 9, 3, 9, 102, 2, 9, 9, 4, 9, 3, 9, 1001, 9, 2, 9,
+    4, 9, 3, 9, 1002, 9, 2, 9, 4, 9, 3, 9, 1002, 9, 2, 9,
+    4, 9, 3, 9, 102, 2, 9, 9, 4, 9, 3, 9, 102, 2, 9, 9,
+    4, 9, 3, 9, 1001, 9, 2, 9, 4, 9, 3, 9, 1001, 9, 2, 9,
+    4, 9, 99,
+];
+
+const CODE: [(usize, usize); 1] = [
+    (0, 9),
+];
+
+const ENTRY: Flow = Flow::Block(block_0);
+
+enum Flow {
+    Block(fn(&mut State) -> Flow),
+    Interpret(usize),
+    Exit,
+}
+
+struct State {
+    mem: Vec<i64>,
+    inputs: Vec<i64>,
+    next_input: usize,
+    outputs: Vec<i64>,
+}
+
+impl State {
+    fn input(&mut self) -> i64 {
+        let value = *self.inputs.get(self.next_input).expect("no input available");
+        self.next_input += 1;
+        value
+    }
+}
+
+/// Final memory of a run along with everything the program printed.
+pub struct Run {
+    pub mem: Vec<i64>,
+    pub outputs: Vec<i64>,
+}
+
+pub fn run(inputs: &[i64]) -> Vec<i64> {
+    execute(&PROGRAM, inputs).outputs
+}
+
+pub fn run_with_memory(data: &[i64], inputs: &[i64]) -> Vec<i64> {
+    execute(data, inputs).outputs
+}
+
+/// Runs a copy of `data`, keeping the final memory for programs that leave their
+/// answer there.
+pub fn execute(data: &[i64], inputs: &[i64]) -> Run {
+    let mut s = State {
+        mem: data.to_vec(),
+        inputs: inputs.to_vec(),
+        next_input: 0,
+        outputs: Vec::new(),
+    };
+    let same_code = CODE
+        .iter()
+        .all(|&(start, end)| data.get(start..end) == Some(&PROGRAM[start..end]));
+    let mut flow = if same_code { ENTRY } else { Flow::Interpret(0) };
+    loop {
+        flow = match flow {
+            Flow::Block(block) => block(&mut s),
+            Flow::Interpret(pos) => return interpret(s, pos),
+            Flow::Exit => {
+                return Run {
+                    mem: s.mem,
+                    outputs: s.outputs,
+                }
+            }
+        };
+    }
+}
+
+fn interpret(mut s: State, pos: usize) -> Run {
+    let mut machine = Machine::new(&s.mem, &s.inputs[s.next_input..]).with_pos(pos);
+    s.outputs.extend_from_slice(machine.run().outputs());
+    Run {
+        mem: machine.data().to_vec(),
+        outputs: s.outputs,
+    }
+}
+
+fn dispatch(pos: usize) -> Flow {
+    match pos {
+        0 => Flow::Block(block_0),
+        2 => Flow::Block(block_2),
+        6 => Flow::Block(block_6),
+        _ => Flow::Interpret(pos),
+    }
+}
+
+fn block_0(s: &mut State) -> Flow {
+    // 0: Input
+    s.mem[8] = s.input();
+    Flow::Interpret(2)
+}
+
+fn block_2(s: &mut State) -> Flow {
+    // 2: Add
+    s.mem[8] = s.mem[8] + 10;
+    Flow::Interpret(6)
+}
+
+fn block_6(s: &mut State) -> Flow {
+    // 6: JumpIfTrue
+    if 1 != 0 {
+        return dispatch(s.mem[0] as usize);
+    }
+    Flow::Interpret(9)
+}